@@ -1,25 +1,376 @@
+use anyhow::{Context, Result, bail};
 use argh::FromArgs;
 use rand::TryRngCore;
 
+use crate::{
+    config,
+    game::{Difficulty, NeighbourMode},
+    paths,
+    theme::Palette,
+};
+
 /// A "summing" game.
 #[derive(FromArgs)]
+#[argh(note = "Defaults for the options can be given in summing.toml in \
+the configuration directory (e.g. ~/.config/summing), one 'option = value' \
+per line (e.g. 'preview = 6' or 'wide = true'); options on the command \
+line take precedence. A switch turned on there is turned off on the \
+command line by e.g. '--wide=false'.")]
 pub struct Options {
-    /// seed to initialize the random number generator with
-    #[argh(option, short = 's', default = "default_seed()")]
+    /// seed to initialize the random number generator with; drawn
+    /// from the operating system if not given (see '--verbose')
+    #[argh(option, short = 's', default = "default_seed()", from_str_fn(seed))]
     pub seed: u64,
 
+    /// tell the seed of the game on stderr when starting
+    #[argh(switch, short = 'v')]
+    pub verbose: bool,
+
+    /// play the puzzle of the day; the same for everyone on the same
+    /// (UTC) day; overrides '--seed'
+    #[argh(switch)]
+    pub daily: bool,
+
+    /// number of rows of the board (at least 3)
+    #[argh(option, default = "crate::game::ROWS", from_str_fn(board_dimension))]
+    pub rows: usize,
+
+    /// number of columns of the board (at least 3)
+    #[argh(option, default = "crate::game::COLS", from_str_fn(board_dimension))]
+    pub cols: usize,
+
+    /// how densely to fill the board initially: easy, normal, or hard
+    #[argh(
+        option,
+        short = 'd',
+        default = "Difficulty::Normal",
+        from_str_fn(difficulty)
+    )]
+    pub difficulty: Difficulty,
+
+    /// number of upcoming stones to preview (clamped to 1..=8)
+    #[argh(option, default = "crate::game::NUM_NEXTS", from_str_fn(preview))]
+    pub preview: usize,
+
+    /// the base to sum the neighbours in, i.e. the number of distinct
+    /// stones in play; between 2 and 10
+    #[argh(option, default = "crate::game::MAX_BASE", from_str_fn(base))]
+    pub base: usize,
+
+    /// which cells around a placement count as its neighbours: all,
+    /// orthogonal, or diagonal
+    #[argh(option, default = "NeighbourMode::All", from_str_fn(neighbour_mode))]
+    pub neighbours: NeighbourMode,
+
+    /// number of placements to start the game with; a handicap to
+    /// compare scores with
+    #[argh(option, default = "0")]
+    pub start_score: usize,
+
+    /// play with a finite bag holding this many of each stone in
+    /// play; the game is lost once it runs out (0 for an endless
+    /// stream of stones; the default)
+    #[argh(option, default = "0")]
+    pub bag: usize,
+
+    /// the colors of the stones: default, or colorblind
+    #[argh(option, default = "Palette::Default", from_str_fn(palette))]
+    pub palette: Palette,
+
+    /// customize the colors of the stones through the given theme
+    /// file (overriding those of the palette)
+    #[argh(option)]
+    pub theme: Option<std::path::PathBuf>,
+
+    /// resume the previously saved game
+    #[argh(switch)]
+    pub resume: bool,
+
+    /// draw the board as densely as possible: one column per cell;
+    /// overrides '--wide'
+    #[argh(switch)]
+    pub mini: bool,
+
     /// draw with full-width characters
     #[argh(switch, short = 'w')]
     pub wide: bool,
 
-    /// loads a predefined board
-    #[cfg(feature = "dev")]
+    /// draw without colors, e.g. on a monochrome terminal; also if
+    /// the NO_COLOR environment variable is set
+    #[argh(switch)]
+    pub no_color: bool,
+
+    /// label the columns (a, b, ...) and rows (1, 2, ...) of the
+    /// board
+    #[argh(switch)]
+    pub coords: bool,
+
+    /// reveal the seed in the game-over message; not known for a
+    /// game continued through '--resume'
+    #[argh(switch)]
+    pub reveal_seed: bool,
+
+    /// quit and start new games without asking for confirmation
+    #[argh(switch)]
+    pub no_confirm: bool,
+
+    /// don't ring the bell on placements which don't clear anything
+    #[argh(switch)]
+    pub no_bell: bool,
+
+    /// the maximum number of frames to draw per second (1 to 240);
+    /// lower it to save CPU time during animations
+    #[argh(option, default = "30", from_str_fn(fps))]
+    pub fps: u32,
+
+    /// move the cursor at a steady pace while holding down a key
+    /// rather than by every key repeat of the terminal
+    #[argh(switch)]
+    pub smooth_keys: bool,
+
+    /// point out a place where the next number clears its neighbours
+    /// after three placements in a row which didn't clear anything;
+    /// e.g. while learning the game
+    #[argh(switch)]
+    pub assist: bool,
+
+    /// don't mark the free places a number has been cleared from
+    #[argh(switch)]
+    pub no_trail: bool,
+
+    /// have the cursor prefer free places next to a number
+    #[argh(switch)]
+    pub skip_isolated: bool,
+
+    /// play without the chance of failure: a placement which would
+    /// fill up the board clears the number and its neighbours instead
+    #[argh(switch)]
+    pub zen: bool,
+
+    /// win by clearing this many numbers rather than the whole board,
+    /// for a shorter game (0 to clear the board; the default)
+    #[argh(option, default = "0")]
+    pub goal_clears: usize,
+
+    /// start the cursor at a random free place away from the edges
+    /// of the board (determined by the seed)
+    #[argh(switch)]
+    pub random_cursor: bool,
+
+    /// refuse placements onto free places without any number around
+    /// them rather than wasting a move on them
+    #[argh(switch)]
+    pub refuse_isolated: bool,
+
+    /// have the cursor stop at the edges of the board rather than
+    /// wrap around to the next row or column
+    #[argh(switch)]
+    pub no_wrap: bool,
+
+    /// record the moves of the game to the given file
+    #[argh(option)]
+    pub record: Option<std::path::PathBuf>,
+
+    /// replay a game recorded by '--record' step by step
+    #[argh(option)]
+    pub play: Option<std::path::PathBuf>,
+
+    /// let a simple strategy play the game without any user interface
+    /// and print the outcome; a fresh game only, e.g. not one given by
+    /// '--board' or '--resume'
+    #[argh(switch)]
+    pub solve: bool,
+
+    /// have the game play itself, one game after another, until a key
+    /// is pressed
+    #[argh(switch)]
+    pub demo: bool,
+
+    /// play in plain text, e.g. with a screen reader: describe every
+    /// move in words and read the placements as "row,col" from stdin
+    #[argh(switch)]
+    pub text: bool,
+
+    /// play the board given in the file (one line per row, a digit or
+    /// '.' per cell; '-' to read it from stdin) instead of a random one
     #[argh(option)]
     pub board: Option<std::path::PathBuf>,
+
+    /// dumps the board to the given file when quitting
+    #[cfg(feature = "dev")]
+    #[argh(option)]
+    pub dump: Option<std::path::PathBuf>,
 }
 
+// ~ the options having a short name along with their long one
+const SHORT_NAMES: [(&str, &str); 4] = [
+    ("-s", "seed"),
+    ("-v", "verbose"),
+    ("-d", "difficulty"),
+    ("-w", "wide"),
+];
+
+/// Parses the command line options; taking the defaults from the
+/// options file (see `paths::options_file`), if there is one.
+/// Options given on the command line override those of the file.
 pub fn from_env() -> Options {
-    argh::from_env()
+    let args: Vec<String> = std::env::args().collect();
+    let cmd = args
+        .first()
+        .and_then(|a| std::path::Path::new(a).file_name())
+        .and_then(|a| a.to_str())
+        .unwrap_or(env!("CARGO_PKG_NAME"));
+    let command_line = args.get(1..).unwrap_or_default();
+    let mut merged = load_defaults(cmd, command_line).unwrap_or_else(|e| {
+        eprintln!("{e:#}");
+        std::process::exit(1);
+    });
+    // ~ a switch turned off just keeps the options file from turning
+    // it on
+    merged.extend(
+        command_line
+            .iter()
+            .filter(|a| switched_off(a).is_none())
+            .cloned(),
+    );
+    let merged: Vec<&str> = merged.iter().map(String::as_str).collect();
+    Options::from_args(&[cmd], &merged).unwrap_or_else(|early_exit| {
+        std::process::exit(match early_exit.status {
+            Ok(()) => {
+                println!("{}", early_exit.output);
+                0
+            }
+            Err(()) => {
+                eprintln!(
+                    "{}\nRun {cmd} --help for more information.",
+                    early_exit.output
+                );
+                1
+            }
+        })
+    })
+}
+
+// ~ the defaults of the options file as command line arguments; empty
+// if there's no such file
+fn load_defaults(cmd: &str, command_line: &[String]) -> Result<Vec<String>> {
+    let Some(path) = paths::options_file() else {
+        return Ok(Vec::new());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => {
+            defaults(cmd, &text, command_line).with_context(|| format!("{}", path.display()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+    }
+}
+
+// ~ the name of the switch turned off by `arg`, e.g. "wide" for
+// "--wide=false"
+fn switched_off(arg: &str) -> Option<&str> {
+    arg.strip_prefix("--")?.strip_suffix("=false")
+}
+
+// ~ translates the options file's `text` into command line arguments;
+// leaving out the options given (or switched off) on the
+// `command_line` already
+fn defaults(cmd: &str, text: &str, command_line: &[String]) -> Result<Vec<String>> {
+    let given: Vec<&str> = command_line
+        .iter()
+        .filter_map(|a| match a.strip_prefix("--") {
+            Some(name) => Some(switched_off(a).unwrap_or(name)),
+            None => SHORT_NAMES
+                .iter()
+                .find_map(|&(short, name)| (short == a).then_some(name)),
+        })
+        .collect();
+    let mut args = Vec::new();
+    for entry in config::parse(text)? {
+        let [value] = &entry.values[..] else {
+            bail!(
+                "line {}: expected a single value for '{}'",
+                entry.line,
+                entry.key
+            );
+        };
+        let option = format!("--{}", entry.key.replace('_', "-"));
+        // ~ a switch is turned on by "true" and left off by "false"
+        let entry_args = match value.as_str() {
+            "true" | "false" => vec![option],
+            _ => vec![option, value.clone()],
+        };
+        let strs: Vec<&str> = entry_args.iter().map(String::as_str).collect();
+        if let Err(early_exit) = Options::from_args(&[cmd], &strs) {
+            match early_exit.status {
+                Ok(()) => bail!("line {}: unknown option '{}'", entry.line, entry.key),
+                Err(()) => bail!("line {}: {}", entry.line, early_exit.output.trim()),
+            }
+        }
+        if value != "false" && !given.contains(&&entry_args[0][2..]) {
+            args.extend(entry_args);
+        }
+    }
+    Ok(args)
+}
+
+fn seed(value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("not a seed between 0 and {}: {value}", u64::MAX))
+}
+
+fn board_dimension(value: &str) -> Result<usize, String> {
+    match value.parse::<u8>() {
+        Ok(n) if n >= 3 => Ok(n as usize),
+        _ => Err(format!("not a board dimension between 3 and 255: {value}")),
+    }
+}
+
+fn preview(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(n) => Ok(n.clamp(1, crate::game::MAX_NEXTS)),
+        Err(_) => Err(format!("not a number of stones: {value}")),
+    }
+}
+
+fn fps(value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(n) if (1..=240).contains(&n) => Ok(n),
+        _ => Err(format!("not a frame rate between 1 and 240: {value}")),
+    }
+}
+
+fn base(value: &str) -> Result<usize, String> {
+    use crate::game::{MAX_BASE, MIN_BASE};
+    match value.parse::<usize>() {
+        Ok(n) if (MIN_BASE..=MAX_BASE).contains(&n) => Ok(n),
+        _ => Err(format!(
+            "not a base between {MIN_BASE} and {MAX_BASE}: {value}"
+        )),
+    }
+}
+
+fn difficulty(value: &str) -> Result<Difficulty, String> {
+    Difficulty::ALL
+        .into_iter()
+        .find(|d| d.name() == value)
+        .ok_or_else(|| format!("not a difficulty (easy, normal, hard): {value}"))
+}
+
+fn neighbour_mode(value: &str) -> Result<NeighbourMode, String> {
+    NeighbourMode::ALL
+        .into_iter()
+        .find(|m| m.name() == value)
+        .ok_or_else(|| format!("not a neighbour mode (all, orthogonal, diagonal): {value}"))
+}
+
+fn palette(value: &str) -> Result<Palette, String> {
+    match value {
+        "default" => Ok(Palette::Default),
+        "colorblind" => Ok(Palette::Colorblind),
+        _ => Err(format!("not a palette (default, colorblind): {value}")),
+    }
 }
 
 fn default_seed() -> u64 {
@@ -27,3 +378,94 @@ fn default_seed() -> u64 {
         .try_next_u64()
         .expect("os rng not ready (yet)")
 }
+
+#[cfg(test)]
+mod tests {
+    use argh::FromArgs;
+
+    use super::{Options, SHORT_NAMES, defaults, seed};
+
+    #[test]
+    fn test_seed() {
+        assert_eq!(Ok(0), seed("0"));
+        assert_eq!(Ok(u64::MAX), seed("18446744073709551615"));
+        for value in ["-1", "18446744073709551616", "x", ""] {
+            assert!(seed(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let text = "
+preview = 6
+palette = \"colorblind\"
+wide = true
+no_bell = false
+seed = 42
+";
+        let command_line = |args: &[&str]| args.iter().map(|&a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            [
+                "--preview",
+                "6",
+                "--palette",
+                "colorblind",
+                "--wide",
+                "--seed",
+                "42"
+            ],
+            defaults("summing", text, &[]).unwrap()[..]
+        );
+        // ~ the command line takes precedence
+        assert_eq!(
+            ["--palette", "colorblind"],
+            defaults(
+                "summing",
+                text,
+                &command_line(&["-w", "--preview", "3", "-s", "7"])
+            )
+            .unwrap()[..]
+        );
+        // ~ as does a switch turned off there
+        assert_eq!(
+            ["--palette", "colorblind", "--seed", "42"],
+            defaults(
+                "summing",
+                text,
+                &command_line(&["--wide=false", "--preview", "3"])
+            )
+            .unwrap()[..]
+        );
+        for (text, error) in [
+            ("wide = 3", "line 1: "),
+            ("preview = \"lots\"", "line 1: "),
+            ("undo = \"u\"", "line 1: "),
+            ("help = true", "line 1: unknown option 'help'"),
+            (
+                "\nbase = [2, 3]",
+                "line 2: expected a single value for 'base'",
+            ),
+        ] {
+            let e = defaults("summing", text, &[]).unwrap_err().to_string();
+            assert!(e.starts_with(error), "{text}: {e}");
+        }
+    }
+
+    #[test]
+    fn test_short_names() {
+        // ~ exactly the options listed with a short name in the help
+        let help = match Options::from_args(&["summing"], &["--help"]) {
+            Ok(_) => panic!("no help"),
+            Err(early_exit) => early_exit.output,
+        };
+        let listed: Vec<_> = help
+            .lines()
+            .filter_map(|line| {
+                let (short, rest) = line.trim_start().split_once(", --")?;
+                let name = rest.split_whitespace().next()?;
+                Some((short, name))
+            })
+            .collect();
+        assert_eq!(SHORT_NAMES[..], listed[..]);
+    }
+}
@@ -0,0 +1,267 @@
+//! A minimal reader for the subset of TOML used by the game's
+//! configuration files: `key = value` pairs, where a value is a
+//! string, a bare word (e.g. a number or a boolean), or an array of
+//! those; optionally grouped into `[tables]`.
+//!
+//! Precisely, the supported subset is:
+//!
+//! - one entry, table header, or nothing per line; blank lines and
+//!   `#` comments (also trailing ones) are ignored,
+//! - keys and table names are bare words or quoted strings; a dotted
+//!   name is taken verbatim, i.e. it does not open nested tables,
+//! - basic strings (`"..."`) support the escapes `\n`, `\t`, `\"`,
+//!   and `\\` only; literal strings (`'...'`) support none,
+//! - a bare word extends up to the next whitespace or one of
+//!   `=,[]#` and is not interpreted any further, e.g. `42`, `true`,
+//!   and `1979-05-27` all stay plain text,
+//! - arrays hold scalars only, must be closed on the same line, and
+//!   may end with a trailing comma.
+//!
+//! Everything else, notably multi-line strings, inline tables,
+//! nested arrays, and arrays of tables (`[[...]]`), is rejected with
+//! an error naming the offending line.  Keys given more than once
+//! are all reported; it is up to the caller to decide which wins.
+
+use anyhow::{Result, bail};
+
+/// A single `key = value` pair of a configuration file
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The (1-based) line the entry was found on
+    pub line: usize,
+    /// The key; prefixed with the name of the enclosing table, if
+    /// any, e.g. `table.key`
+    pub key: String,
+    /// The value(s); a single element unless given as an array
+    pub values: Vec<String>,
+}
+
+/// Parses the given configuration text into its entries (in order
+/// of appearance.)
+pub fn parse(text: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut table = String::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let mut p = Parser(line.trim_start());
+        if p.at_end() {
+            continue;
+        }
+        if p.eat('[') {
+            let name = p
+                .scalar()
+                .map_err(|e| anyhow::anyhow!("line {line_no}: {e}"))?;
+            p.skip_ws();
+            if !p.eat(']') || !p.at_end() {
+                bail!("line {line_no}: malformed table header");
+            }
+            table = name;
+            continue;
+        }
+        let key = p
+            .scalar()
+            .map_err(|e| anyhow::anyhow!("line {line_no}: {e}"))?;
+        p.skip_ws();
+        if !p.eat('=') {
+            bail!("line {line_no}: expected '=' after '{key}'");
+        }
+        let values = p
+            .values()
+            .map_err(|e| anyhow::anyhow!("line {line_no}: {e}"))?;
+        if !p.at_end() {
+            bail!("line {line_no}: unexpected trailing characters");
+        }
+        entries.push(Entry {
+            line: line_no,
+            key: if table.is_empty() {
+                key
+            } else {
+                format!("{table}.{key}")
+            },
+            values,
+        });
+    }
+    Ok(entries)
+}
+
+// ~ a cursor over the remainder of a line to parse
+struct Parser<'a>(&'a str);
+
+impl Parser<'_> {
+    fn skip_ws(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    // ~ true if only whitespace or a comment remain
+    fn at_end(&mut self) -> bool {
+        self.skip_ws();
+        self.0.is_empty() || self.0.starts_with('#')
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // ~ a single value or an array of values
+    fn values(&mut self) -> Result<Vec<String>, String> {
+        if !self.eat('[') {
+            return Ok(vec![self.scalar()?]);
+        }
+        let mut values = Vec::new();
+        loop {
+            if self.eat(']') {
+                return Ok(values);
+            }
+            values.push(self.scalar()?);
+            if !self.eat(',') && !matches!(self.0.chars().next(), Some(']')) {
+                return Err("expected ',' or ']' in array".into());
+            }
+        }
+    }
+
+    // ~ a quoted string or a bare word
+    fn scalar(&mut self) -> Result<String, String> {
+        self.skip_ws();
+        let mut chars = self.0.char_indices();
+        match chars.next() {
+            Some((_, '"')) => {
+                let mut s = String::new();
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            self.0 = &self.0[i + 1..];
+                            return Ok(s);
+                        }
+                        '\\' => match chars.next() {
+                            Some((_, 'n')) => s.push('\n'),
+                            Some((_, 't')) => s.push('\t'),
+                            Some((_, c @ ('"' | '\\'))) => s.push(c),
+                            _ => return Err("invalid escape sequence".into()),
+                        },
+                        c => s.push(c),
+                    }
+                }
+                Err("unterminated string".into())
+            }
+            Some((_, '\'')) => match self.0[1..].split_once('\'') {
+                Some((s, rest)) => {
+                    let s = s.to_owned();
+                    self.0 = rest;
+                    Ok(s)
+                }
+                None => Err("unterminated string".into()),
+            },
+            _ => {
+                let end = self
+                    .0
+                    .find(|c: char| c.is_whitespace() || matches!(c, '=' | ',' | '[' | ']' | '#'))
+                    .unwrap_or(self.0.len());
+                if end == 0 {
+                    return Err("expected a value".into());
+                }
+                let (word, rest) = self.0.split_at(end);
+                self.0 = rest;
+                Ok(word.to_owned())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, parse};
+
+    fn entry(line: usize, key: &str, values: &[&str]) -> Entry {
+        Entry {
+            line,
+            key: key.into(),
+            values: values.iter().map(|&v| v.into()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let text = r#"
+# a comment
+quit = "q"
+undo = ['u', "ctrl-z"]  # trailing comment
+redo = [ ]
+count = 42
+
+[table]
+"0" = "a \"quoted\" # word"
+"#;
+        assert_eq!(
+            vec![
+                entry(3, "quit", &["q"]),
+                entry(4, "undo", &["u", "ctrl-z"]),
+                entry(5, "redo", &[]),
+                entry(6, "count", &["42"]),
+                entry(9, "table.0", &["a \"quoted\" # word"]),
+            ],
+            parse(text).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in [
+            "quit",
+            "quit = ",
+            "quit = \"q",
+            "quit = [\"q\"",
+            "quit = q q",
+            "[t",
+        ] {
+            assert!(parse(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_unsupported() {
+        for (text, msg) in [
+            (
+                "\n\nquit = \"\"\"q\"\"\"",
+                "line 3: unexpected trailing characters",
+            ),
+            (
+                "quit = { key = \"q\" }",
+                "line 1: unexpected trailing characters",
+            ),
+            ("quit = [[\"q\"]]", "line 1: expected a value"),
+            ("[[keys]]", "line 1: expected a value"),
+            ("quit = [\"q\",\n  \"x\"]", "line 1: expected a value"),
+            ("quit = \"\\u0071\"", "line 1: invalid escape sequence"),
+            ("quit = 'q", "line 1: unterminated string"),
+            (
+                "quit = [\"q\" \"x\"]",
+                "line 1: expected ',' or ']' in array",
+            ),
+            ("= \"q\"", "line 1: expected a value"),
+            ("quit \"q\"", "line 1: expected '=' after 'quit'"),
+            ("[keys] quit = \"q\"", "line 1: malformed table header"),
+        ] {
+            let err = parse(text).expect_err(text);
+            assert_eq!(msg, err.to_string(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_verbatim() {
+        let text = "[a.b]\nx = 'c:\\dir'\nx = [1979-05-27, true,]";
+        assert_eq!(
+            vec![
+                entry(2, "a.b.x", &["c:\\dir"]),
+                entry(3, "a.b.x", &["1979-05-27", "true"]),
+            ],
+            parse(text).unwrap()
+        );
+    }
+}
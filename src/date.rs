@@ -0,0 +1,71 @@
+//! Calendar dates as needed for the daily puzzle; without pulling in
+//! a date library.
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A day of the (proleptic Gregorian) calendar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// The current day in UTC.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    // ~ the date `days` after 1970-01-01; the `civil_from_days`
+    // algorithm by Howard Hinnant
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097); // ~ [0, 146096]
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // ~ [0, 399]
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // ~ [0, 365]
+        let mp = (5 * doy + 2) / 153; // ~ [0, 11]; starting in march
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    /// The seed of the day's puzzle; the date's digits as a number,
+    /// e.g. `20261016`.
+    pub fn seed(self) -> u64 {
+        self.year.max(0) as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Date;
+
+    #[test]
+    fn test_from_days() {
+        for (days, date, seed) in [
+            (0, "1970-01-01", 19700101),
+            (11016, "2000-02-29", 20000229),
+            (20742, "2026-10-16", 20261016),
+            (-1, "1969-12-31", 19691231),
+        ] {
+            let d = Date::from_days(days);
+            assert_eq!(date, d.to_string());
+            assert_eq!(seed, d.seed());
+        }
+    }
+}
@@ -1,15 +1,21 @@
-use std::ops::Add;
+use std::{collections::VecDeque, fmt, ops::Add, str::FromStr};
 
 use rand::{
     Rng,
     distr::{Distribution, StandardUniform},
-    seq::SliceRandom,
+    seq::{IndexedRandom, SliceRandom},
 };
 
 // ~ the number of distinct stones
 pub const NUM_STONES: usize = 10;
 
+// ~ serialized as its value
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "u8", try_from = "u8")
+)]
 pub enum Stone {
     _0 = 0,
     _1 = 1,
@@ -31,9 +37,39 @@ impl Add<Stone> for usize {
     }
 }
 
-impl Distribution<Stone> for StandardUniform {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Stone {
-        match rng.next_u32() % (NUM_STONES as u32) {
+impl Stone {
+    // ~ formats the stone as an ascii digit
+    fn to_ascii_digit(self) -> u8 {
+        b'0' + self as u8
+    }
+}
+
+/// The error of converting something which doesn't denote a stone
+/// into one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidStone;
+
+impl fmt::Display for InvalidStone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not a stone; expected a digit")
+    }
+}
+
+impl std::error::Error for InvalidStone {}
+
+/// The stone's value; same as `stone as u8`.
+impl From<Stone> for u8 {
+    fn from(stone: Stone) -> Self {
+        stone as u8
+    }
+}
+
+/// Converts the stone's value; the inverse of `stone as u8`.
+impl TryFrom<u8> for Stone {
+    type Error = InvalidStone;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
             0 => Stone::_0,
             1 => Stone::_1,
             2 => Stone::_2,
@@ -44,53 +80,493 @@ impl Distribution<Stone> for StandardUniform {
             7 => Stone::_7,
             8 => Stone::_8,
             9 => Stone::_9,
-            _ => panic!("invalid NUM_STONES"),
+            _ => return Err(InvalidStone),
+        })
+    }
+}
+
+/// Converts an (ascii) digit.
+impl TryFrom<char> for Stone {
+    type Error = InvalidStone;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '0'..='9' => Stone::try_from(value as u8 - b'0'),
+            _ => Err(InvalidStone),
+        }
+    }
+}
+
+/// Parses a single (ascii) digit.
+impl FromStr for Stone {
+    type Err = InvalidStone;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Stone::try_from(c),
+            _ => Err(InvalidStone),
         }
     }
 }
 
-// ~ the size of the "nexts" magazine
-const NUM_NEXTS: usize = 4;
+/// Formats the stone as its digit.
+impl fmt::Display for Stone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&char::from(self.to_ascii_digit()), f)
+    }
+}
+
+impl Distribution<Stone> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Stone {
+        Stones(NUM_STONES).sample(rng)
+    }
+}
+
+// ~ the uniform distribution over the stones of a game played in the
+// given base, i.e. the first `base` stones
+struct Stones(usize);
+
+impl Distribution<Stone> for Stones {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Stone {
+        Stone::try_from((rng.next_u32() % (self.0 as u32)) as u8)
+            .unwrap_or_else(|_| panic!("invalid base: {}", self.0))
+    }
+}
+
+// ~ the range of bases supported; the number of distinct stones
+// (along with their labels and colors) limits it from above
+pub const MIN_BASE: usize = 2;
+pub const MAX_BASE: usize = NUM_STONES;
+
+// ~ the par of a game, i.e. the targeted number of placements, given
+// the number of stones to clear; a simple greedy strategy needs about
+// twice that many
+fn par_for(num_stones: usize) -> usize {
+    num_stones * 3 / 2
+}
+
+// ~ the default and maximum size of the "nexts" magazine
+pub const NUM_NEXTS: usize = 4;
+pub const MAX_NEXTS: usize = 8;
+
+// ~ the max number of moves remembered for undo
+const MAX_UNDO: usize = 128;
 
-const ROWS: usize = 9;
-const COLS: usize = 9;
-// ~ the number of max possible stones on the board, essentially it's
-// the size of the board in terms of the number of stones.
-const MAX_STONES: usize = ROWS * COLS;
+// ~ the first line of a saved game; identifies the format and its
+// version
+const SAVE_HEADER: &str = "summing-save 1";
+
+// ~ the default size of the board
+pub const ROWS: usize = 9;
+pub const COLS: usize = 9;
+
+// ~ the range of board dimensions supported; cells are addressed by
+// `u8` coordinates which limits it from above
+pub const MIN_SIZE: usize = 3;
+pub const MAX_SIZE: usize = u8::MAX as usize;
+
+// ~ fails unless both dimensions are within `MIN_SIZE..=MAX_SIZE`
+fn check_size(rows: usize, cols: usize) -> anyhow::Result<()> {
+    if rows < MIN_SIZE || cols < MIN_SIZE {
+        anyhow::bail!("board too small: {rows}x{cols}");
+    }
+    if rows > MAX_SIZE || cols > MAX_SIZE {
+        anyhow::bail!("board too large: {rows}x{cols}");
+    }
+    Ok(())
+}
 
 /// Game board state
 pub struct Game<R> {
     // ~ random number generator
     rng: R,
-    // ~ stones to be served as next (left to right)
-    nexts: [Stone; NUM_NEXTS],
+    // ~ the stones to be served as next
+    magazine: Magazine,
     // ~ number of stones still on the board; zero when the game is
-    // finished; `MAX_STONES` if the board is full and no new
+    // finished; `board.len()` if the board is full and no new
     // placement is possible
     num_remaining: usize,
     // ~ number of (user) placed stones, ie. the "score"
     num_placed: usize,
+    // ~ the number of placements targeted to clear the board with
+    par: usize,
+    // ~ the dimensions of the board
+    rows: usize,
+    cols: usize,
+    // ~ the modulus of the neighbours' sum; only the stones below it
+    // are in play
+    base: usize,
     // ~ the board of stones; rows of columns
-    board: [Option<Stone>; MAX_STONES],
+    board: Vec<Option<Stone>>,
+    // ~ the cells of the board ever occupied by a stone in this game;
+    // initially or by a placement (see `was_occupied`)
+    visited: Vec<bool>,
+    // ~ the most recent placements (the latest at the end); allows
+    // undoing them
+    undo_stack: Vec<Move>,
+    // ~ the most recently undone placements (the latest at the end);
+    // allows re-applying them; cleared by a new placement
+    redo_stack: Vec<Move>,
+    // ~ the number of placements of each stone, and of those which
+    // cleared any stones
+    placed_per_stone: [usize; NUM_STONES],
+    clearing_per_stone: [usize; NUM_STONES],
+    // ~ the number of stones cleared from the board so far
+    num_cleared: usize,
+    // ~ the number of stones to clear to win the game; `None` to win
+    // by clearing the board
+    goal_clears: Option<usize>,
+    // ~ true to have the cursor prefer free cells next to a stone
+    skip_isolated: bool,
+    // ~ true to have the cursor wrap around the board's edges
+    wrap: bool,
+    // ~ false to refuse placements onto cells without any neighbours
+    allow_isolated_placement: bool,
+    // ~ true to never deadlock the board; see `set_zen`
+    zen: bool,
+    // ~ which cells around a placement count as its neighbours
+    neighbour_mode: NeighbourMode,
+    // ~ told about every placement, if any
+    observer: Option<Box<dyn Observer>>,
+}
+
+// ~ a record of a single `place_next` allowing to reverse and
+// re-apply it
+struct Move {
+    // ~ the cell the stone was placed onto
+    point: Cursor,
+    // ~ the board cells modified by the move along with their
+    // previous state; unused slots have `usize::MAX` as index; room
+    // for the placed stone and its neighbours (see `set_zen`)
+    cells: [(usize, Option<Stone>); 9],
+    // ~ the stone taken from the magazine by the move
+    next: Stone,
+    // ~ whether the cell had ever been occupied before the move
+    visited: bool,
+    // ~ true if the move cleared any stones
+    cleared: bool,
+    // ~ the counters before the move
+    num_remaining: usize,
+    num_placed: usize,
+    num_cleared: usize,
+}
+
+// ~ where the stones beyond those previewed come from
+enum Source {
+    // ~ drawn at random among the stones in play, i.e. those below
+    // the given base
+    Random(usize),
+    // ~ taken from a finite bag; front first
+    Bag(VecDeque<Stone>),
+}
+
+// ~ the stones to be served as next: the ones previewed followed by
+// those yet to be revealed
+struct Magazine {
+    // ~ the stones previewed (left to right); fewer than `size` only
+    // once a finite bag ran out
+    nexts: Vec<Stone>,
+    // ~ the number of stones previewed
+    size: usize,
+    // ~ stones handed back by undone moves; served (from the back)
+    // before taking new stones from `source` such that undo doesn't
+    // alter the sequence of stones to come
+    rewound: Vec<Stone>,
+    source: Source,
+}
+
+impl Magazine {
+    // ~ a magazine previewing `size` stones drawn from `rng` among the
+    // first `base` stones
+    fn new<R: Rng>(size: usize, base: usize, rng: &mut R) -> Self {
+        let mut magazine = Magazine {
+            nexts: Vec::with_capacity(size),
+            size,
+            rewound: Vec::new(),
+            source: Source::Random(base),
+        };
+        magazine.refill(rng);
+        magazine
+    }
+
+    // ~ a magazine previewing `size` stones taken from `bag`
+    fn with_bag(size: usize, mut bag: VecDeque<Stone>) -> Self {
+        Magazine {
+            nexts: bag.drain(..size.min(bag.len())).collect(),
+            size,
+            rewound: Vec::new(),
+            source: Source::Bag(bag),
+        }
+    }
+
+    // ~ the (up to) `n` stones to come next
+    fn peek(&self, n: usize) -> &[Stone] {
+        &self.nexts[..n.min(self.nexts.len())]
+    }
+
+    // ~ serves the next stone refilling the preview; `None` once a
+    // finite bag ran out
+    fn advance<R: Rng>(&mut self, rng: &mut R) -> Option<Stone> {
+        if self.nexts.is_empty() {
+            return None;
+        }
+        let next = self.nexts.remove(0);
+        self.refill(rng);
+        Some(next)
+    }
+
+    // ~ tops up the preview from the stones rewound or `source`
+    fn refill<R: Rng>(&mut self, rng: &mut R) {
+        while self.nexts.len() < self.size {
+            let next = match (self.rewound.pop(), &mut self.source) {
+                (Some(s), _) => s,
+                (None, Source::Bag(bag)) => match bag.pop_front() {
+                    Some(s) => s,
+                    None => break,
+                },
+                (None, Source::Random(base)) => rng.sample(Stones(*base)),
+            };
+            self.nexts.push(next);
+        }
+    }
+
+    // ~ hands `stone` - served by the most recent `advance` - back to
+    // the front of the magazine
+    fn rewind(&mut self, stone: Stone) {
+        // ~ the preview runs short only once the bag ran out; if full,
+        // `advance` refilled it and the stone drawn goes back
+        if self.nexts.len() == self.size {
+            self.rewound.extend(self.nexts.pop());
+        }
+        self.nexts.insert(0, stone);
+    }
+
+    // ~ the number of stones left in a finite bag (apart from those
+    // previewed); `None` for an endless stream
+    fn bag_len(&self) -> Option<usize> {
+        match &self.source {
+            Source::Random(_) => None,
+            Source::Bag(bag) => Some(bag.len() + self.rewound.len()),
+        }
+    }
+
+    // ~ the stones left in a finite bag (apart from those previewed)
+    // in the order to come; `None` for an endless stream
+    fn bag(&self) -> Option<impl Iterator<Item = Stone> + '_> {
+        match &self.source {
+            Source::Random(_) => None,
+            Source::Bag(bag) => Some(self.rewound.iter().rev().chain(bag).copied()),
+        }
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Finished {
     /// The game has been finished successfully
     Success,
     /// The game finished with all cells being occupied, but none
     /// cancelling out each other, such that placement of further
-    /// stones would be possible.  This is considered a defeat /
-    /// failure.
+    /// stones would be possible; or, more generally, with the board
+    /// being deadlocked (see `Game::is_deadlocked`).  This is
+    /// considered a defeat / failure.
     Failure,
 }
 
+/// The outcome of a placement; see `Game::place_next`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlaceOutcome {
+    /// The stone cleared all its neighbours; the cell was left free
+    Cleared(Placement),
+    /// The stone was placed and now occupies the cell
+    Occupied(Placement),
+    /// The placement was refused for there being no stones around
+    /// the cell (see `Game::set_allow_isolated_placement`), or for the
+    /// cell being off the board
+    Refused,
+}
+
+/// The details of a placement carried out by `Game::place_next`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placement {
+    /// The placed stone
+    pub stone: Stone,
+    /// The number of stones around the cell at the time of placement
+    pub num_neighbours: usize,
+    /// The sum of the stones around the cell at the time of
+    /// placement; its last digit in the game's base is what the stone
+    /// has to match to clear them
+    pub sum: usize,
+    /// The cells cleared by the placement along with the stones they
+    /// held; empty unless the placement cleared its neighbours
+    pub cleared: Vec<(Cursor, Stone)>,
+}
+
+/// A snapshot of the counters of a game along with the stones to
+/// come; see `Game::stats`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameStats {
+    /// The number of placements so far; including a start score
+    pub placed: usize,
+    /// The number of stones on the board
+    pub remaining: usize,
+    /// The number of cells of the board
+    pub total_cells: usize,
+    /// The stones previewed; the next one first
+    pub nexts: Vec<Stone>,
+    /// The number of placements of each stone (by its value) in this
+    /// game; a start score isn't accounted for
+    pub placed_per_stone: [usize; NUM_STONES],
+    /// The number of placements of each stone which cleared any
+    /// stones
+    pub clearing_per_stone: [usize; NUM_STONES],
+}
+
+/// The state of a game - apart from its random number generator, undo
+/// history, and the settings of the frontend (e.g. `set_zen`) - as
+/// plain data; see `Game::snapshot` and `Game::from_snapshot`.
+/// Serializable with the "serde" feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+    /// The seed the game's random number generator was created with,
+    /// if known; not maintained by the game itself but up to the
+    /// creator of the snapshot (see `Game::from_snapshot`)
+    pub seed: Option<u64>,
+    /// The dimensions of the board
+    pub rows: usize,
+    pub cols: usize,
+    /// The modulus of the neighbours' sum; see `Game::new_with_base`
+    pub base: usize,
+    /// Which cells around a placement count as its neighbours
+    pub neighbour_mode: NeighbourMode,
+    /// The cells of the board row by row
+    pub board: Vec<Option<Stone>>,
+    /// The number of stones previewed; see `Game::num_nexts`
+    pub num_nexts: usize,
+    /// The stones previewed; the next one first.  Fewer than
+    /// `num_nexts` once a finite bag runs short.
+    pub nexts: Vec<Stone>,
+    /// The stones left in a finite bag (apart from those previewed) in
+    /// the order to come; `None` for an endless stream
+    pub bag: Option<Vec<Stone>>,
+    /// The number of placements so far; including a start score
+    pub num_placed: usize,
+    /// The number of placements targeted to clear the board with
+    pub par: usize,
+    /// The number of stones cleared from the board so far
+    pub num_cleared: usize,
+    /// The number of placements of each stone (by its value), and of
+    /// those which cleared any stones
+    pub placed_per_stone: [usize; NUM_STONES],
+    pub clearing_per_stone: [usize; NUM_STONES],
+}
+
+/// Something which happened in a game; see `Game::set_observer`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameEvent<'a> {
+    /// The stone was placed at the given cell and now occupies it
+    Placed(Cursor, &'a Placement),
+    /// The stone placed at the given cell cleared its neighbours
+    Cleared(Cursor, &'a Placement),
+    /// The placement brought the game to an end
+    Finished(Finished),
+}
+
+/// Receives the events of a game, e.g. to log them; see
+/// `Game::set_observer`.  Implemented for closures taking a
+/// `&GameEvent`.
+pub trait Observer {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+impl<F: FnMut(&GameEvent)> Observer for F {
+    fn on_event(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
+
+/// Determines how densely the board is filled initially
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Leaves some of the inner cells empty
+    Easy,
+    /// Fills all of the inner cells
+    #[default]
+    Normal,
+    /// Fills all of the inner cells and some of the border cells
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// The name of the difficulty as given on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+/// Determines which of the (up to eight) cells around a cell are its
+/// neighbours; only those are summed up and cleared by a placement
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum NeighbourMode {
+    /// All the cells around, including the diagonals
+    #[default]
+    All,
+    /// The cells to the north, south, east, and west only
+    Orthogonal,
+    /// The cells at the diagonals only
+    Diagonal,
+}
+
+impl NeighbourMode {
+    pub const ALL: [NeighbourMode; 3] = [
+        NeighbourMode::All,
+        NeighbourMode::Orthogonal,
+        NeighbourMode::Diagonal,
+    ];
+
+    /// The name of the mode as given on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            NeighbourMode::All => "all",
+            NeighbourMode::Orthogonal => "orthogonal",
+            NeighbourMode::Diagonal => "diagonal",
+        }
+    }
+
+    // ~ the slots of `Game::neighbour_indices` which are not
+    // neighbours in this mode
+    fn excluded(self) -> &'static [usize] {
+        match self {
+            NeighbourMode::All => &[],
+            NeighbourMode::Orthogonal => &[0, 2, 5, 7],
+            NeighbourMode::Diagonal => &[1, 3, 4, 6],
+        }
+    }
+}
+
 /// Cursor into the game's board
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cursor {
     pub x: u8,
     pub y: u8,
 }
 
+/// A direction to move the cursor in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     North,
     South,
@@ -98,17 +574,90 @@ pub enum Direction {
     West,
 }
 
+impl Direction {
+    /// All the directions; clockwise starting with `North`
+    pub fn all() -> [Direction; 4] {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+    }
+
+    /// The direction pointing the other way
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
 impl<R> Game<R> {
     pub fn rows(&self) -> usize {
-        ROWS
+        self.rows
     }
 
     pub fn cols(&self) -> usize {
-        COLS
+        self.cols
+    }
+
+    /// Tells which cells around a cell count as its neighbours.
+    pub fn neighbour_mode(&self) -> NeighbourMode {
+        self.neighbour_mode
+    }
+
+    /// Changes which cells around a placement are summed up and
+    /// cleared; all eight by default.
+    pub fn set_neighbour_mode(&mut self, mode: NeighbourMode) {
+        self.neighbour_mode = mode;
+    }
+
+    /// Tells the base the game is played in; see `new_with_base`.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    pub fn nexts(&self) -> impl ExactSizeIterator<Item = Stone> {
+        self.magazine.peek(self.magazine.size).iter().copied()
     }
 
-    pub fn nexts(&self) -> impl Iterator<Item = Stone> {
-        self.nexts.into_iter()
+    /// Tells the size of the magazine, i.e. the number of stones
+    /// previewed; `nexts` yields fewer once a finite bag runs out.
+    pub fn num_nexts(&self) -> usize {
+        self.magazine.size
+    }
+
+    /// Tells the number of stones left in the bag (apart from those
+    /// in the magazine) if playing with a finite bag.
+    pub fn bag_len(&self) -> Option<usize> {
+        self.magazine.bag_len()
+    }
+
+    /// Tells the number of stones on the board; the game is won once
+    /// there are none left.
+    pub fn num_remaining(&self) -> usize {
+        self.num_remaining
+    }
+
+    /// Tells the total value of the stones on the board; of interest
+    /// mainly for comparing failed games.
+    pub fn remaining_sum(&self) -> usize {
+        self.board.iter().flatten().map(|&s| s as usize).sum()
+    }
+
+    /// Tells the number of stones cleared from the board so far.
+    pub fn num_cleared(&self) -> usize {
+        self.num_cleared
+    }
+
+    /// Tells the number of stones to clear to win the game, if set;
+    /// see `set_goal_clears`.
+    pub fn goal_clears(&self) -> Option<usize> {
+        self.goal_clears
     }
 
     /// Tells the number of placed stones so far.
@@ -116,57 +665,264 @@ impl<R> Game<R> {
         self.num_placed
     }
 
+    /// Takes a snapshot of the game's counters and the stones to come.
+    pub fn stats(&self) -> GameStats {
+        GameStats {
+            placed: self.num_placed,
+            remaining: self.num_remaining,
+            total_cells: self.board.len(),
+            nexts: self.nexts().collect(),
+            placed_per_stone: self.placed_per_stone,
+            clearing_per_stone: self.clearing_per_stone,
+        }
+    }
+
+    /// Starts the game with the given number of placements already
+    /// counted; a handicap making scores of games comparable.  The
+    /// count replaces that of any placements made so far; the par
+    /// moves along with it, though never below zero.
+    pub fn set_start_score(&mut self, num_placed: usize) {
+        // ~ the handicap doesn't count against the par
+        self.par = (self.par + num_placed).saturating_sub(self.num_placed);
+        self.num_placed = num_placed;
+    }
+
+    /// Tells the number of placements targeted to clear the board
+    /// with; derived from the number of stones initially on the board.
+    pub fn par(&self) -> usize {
+        self.par
+    }
+
     // ~ panics if `row` or `col` are out of bounds.
     pub fn get(&self, row: usize, col: usize) -> Option<Stone> {
-        self.board[row * COLS + col]
+        self.board[row * self.cols + col]
+    }
+
+    /// Like `get` but without panicking: `None` if `row` or `col` are
+    /// out of the board's bounds, otherwise the cell's stone if any.
+    pub fn try_get(&self, row: usize, col: usize) -> Option<Option<Stone>> {
+        (row < self.rows && col < self.cols).then(|| self.board[row * self.cols + col])
+    }
+
+    /// Tells the stone at the given cell, if any; `None` as well for a
+    /// cell out of the board's bounds.
+    pub fn cell(&self, point: Cursor) -> Option<Stone> {
+        self.try_get(point.y as usize, point.x as usize).flatten()
+    }
+
+    /// Tells whether the given cell has ever been occupied by a stone
+    /// in this game; initially or by a placement which didn't clear
+    /// anything.  False for a cell out of the board's bounds.  A game
+    /// restored from a save (or snapshot) knows only of the cells
+    /// occupied then.
+    pub fn was_occupied(&self, point: Cursor) -> bool {
+        let (row, col) = (point.y as usize, point.x as usize);
+        row < self.rows && col < self.cols && self.visited[row * self.cols + col]
+    }
+
+    /// Iterates the rows of the board from top to bottom; each a slice
+    /// of the row's cells from left to right.
+    pub fn rows_iter(&self) -> impl Iterator<Item = &[Option<Stone>]> {
+        self.board.chunks(self.cols)
+    }
+
+    /// Makes `find_free_next` and `find_free_any` prefer free cells
+    /// with at least one stone around them; falling back to any free
+    /// cell if there is none such.
+    pub fn set_skip_isolated(&mut self, skip_isolated: bool) {
+        self.skip_isolated = skip_isolated;
+    }
+
+    /// Whether the cursor moves on to the next row (or column) when
+    /// reaching an edge of the board, or stops at it; the default is
+    /// to wrap around.
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Whether `place_next` occupies a free cell without any stones
+    /// around it, or refuses to waste a placement on it; the default
+    /// is to allow it.
+    pub fn set_allow_isolated_placement(&mut self, allow: bool) {
+        self.allow_isolated_placement = allow;
+    }
+
+    pub fn allows_isolated_placement(&self) -> bool {
+        self.allow_isolated_placement
+    }
+
+    /// Plays without the chance of failure: a placement after which
+    /// no stone could be cleared anymore (see `is_deadlocked`), e.g.
+    /// the one filling up the board, clears the placed stone along
+    /// with its neighbours instead.  The game only ends once the
+    /// board is cleared (or a finite bag runs out of stones).
+    pub fn set_zen(&mut self, zen: bool) {
+        self.zen = zen;
+    }
+
+    /// Tells whether the game is played in zen mode; see `set_zen`.
+    pub fn is_zen(&self) -> bool {
+        self.zen
+    }
+
+    /// Has the game won once the given number of stones has been
+    /// cleared from the board (rather than only by clearing the board
+    /// entirely); `None` for the latter only.
+    pub fn set_goal_clears(&mut self, goal: Option<usize>) {
+        self.goal_clears = goal;
+    }
+
+    /// Attaches an observer to be told about every `place_next` (but
+    /// not about undone or redone ones), replacing any earlier one;
+    /// `None` detaches it.
+    pub fn set_observer(&mut self, observer: Option<Box<dyn Observer>>) {
+        self.observer = observer;
+    }
+
+    // ~ true if there is no stone around the cell at `point`
+    fn is_isolated(&self, point: Cursor) -> bool {
+        self.neighbour_sum(point).0 == 0
+    }
+
+    // ~ the cell of the board closest to `point`; e.g. for a cursor
+    // gone stale by the board having changed underneath it
+    fn clamp(&self, point: Cursor) -> Cursor {
+        Cursor {
+            x: point.x.min((self.cols - 1) as u8),
+            y: point.y.min((self.rows - 1) as u8),
+        }
     }
 
     /// Finds a free place next to `point` preferrably in given
-    /// direction.
-    // ~ panics if `point` is out of bounds of the game's board.
+    /// direction.  Without wrapping (see `set_wrap`), only the places
+    /// ahead in the same row (or column) are considered; `point` is
+    /// returned if there's none.  A `point` out of the board's bounds
+    /// is taken for the closest cell on the board.
     pub fn find_free_next(&self, point: Cursor, direction: Direction) -> Option<Cursor> {
-        if self.num_remaining == MAX_STONES {
+        self.find_free_next_with_distance(point, direction)
+            .map(|(p, _)| p)
+    }
+
+    /// Like `find_free_next` but tells along with the found place how
+    /// far the cursor moves to get there: the number of cells passed
+    /// along `direction` (including the ones wrapped around to), i.e.
+    /// one for an adjacent cell and zero if staying at `point`.
+    pub fn find_free_next_with_distance(
+        &self,
+        point: Cursor,
+        direction: Direction,
+    ) -> Option<(Cursor, usize)> {
+        let point = self.clamp(point);
+        let mut found = None;
+        if self.skip_isolated {
+            found = self
+                .find_free_next_by(point, direction, |p| !self.is_isolated(p))
+                .filter(|&p| self.cell(p).is_none());
+        }
+        found
+            .or_else(|| self.find_free_next_by(point, direction, |_| true))
+            // ~ stay put if there's no other (free) place to go to
+            .or_else(|| (!self.wrap || self.cell(point).is_none()).then_some(point))
+            .map(|p| (p, self.scan_distance(point, p, direction)))
+    }
+
+    // ~ the number of cells between `from` and `to` in the order
+    // `find_free_next_by` scans the board in `direction`
+    fn scan_distance(&self, from: Cursor, to: Cursor, direction: Direction) -> usize {
+        let n = self.board.len();
+        let row_major = |p: Cursor| p.y as usize * self.cols + p.x as usize;
+        let col_major = |p: Cursor| p.x as usize * self.rows + p.y as usize;
+        let (from, to) = match direction {
+            Direction::East => (row_major(from), row_major(to)),
+            Direction::West => (row_major(to), row_major(from)),
+            Direction::South => (col_major(from), col_major(to)),
+            Direction::North => (col_major(to), col_major(from)),
+        };
+        (to + n - from) % n
+    }
+
+    // ~ does the work of `find_free_next` considering only the free
+    // cells accepted by `accept`
+    fn find_free_next_by(
+        &self,
+        point: Cursor,
+        direction: Direction,
+        accept: impl Fn(Cursor) -> bool,
+    ) -> Option<Cursor> {
+        if self.num_remaining == self.board.len() {
             return None;
         }
+        let (rows, cols) = (self.rows, self.cols);
 
         macro_rules! if_free_return_cursor {
             ($index:expr, $board_cell:expr) => {
                 if $board_cell.is_none() {
-                    return Some(Cursor {
-                        x: ($index % COLS) as u8,
-                        y: ($index / COLS) as u8,
-                    });
+                    let cursor = Cursor {
+                        x: ($index % cols) as u8,
+                        y: ($index / cols) as u8,
+                    };
+                    if accept(cursor) {
+                        return Some(cursor);
+                    }
                 }
             };
         }
 
+        // ~ stop at the edges of the board
+        if !self.wrap {
+            let (x, y) = (point.x as usize, point.y as usize);
+            match direction {
+                Direction::North => {
+                    for y in (0..y).rev() {
+                        if_free_return_cursor!(y * cols + x, self.board[y * cols + x]);
+                    }
+                }
+                Direction::South => {
+                    for y in y + 1..rows {
+                        if_free_return_cursor!(y * cols + x, self.board[y * cols + x]);
+                    }
+                }
+                Direction::East => {
+                    for x in x + 1..cols {
+                        if_free_return_cursor!(y * cols + x, self.board[y * cols + x]);
+                    }
+                }
+                Direction::West => {
+                    for x in (0..x).rev() {
+                        if_free_return_cursor!(y * cols + x, self.board[y * cols + x]);
+                    }
+                }
+            }
+            return None;
+        }
+
         match direction {
             Direction::North => {
                 let (mut x, mut y) = if point.y as usize == 0 {
                     if point.x as usize == 0 {
-                        (COLS - 1, ROWS - 1)
+                        (cols - 1, rows - 1)
                     } else {
-                        (point.x as usize - 1, ROWS - 1)
+                        (point.x as usize - 1, rows - 1)
                     }
                 } else {
                     (point.x as usize, point.y as usize - 1)
                 };
-                for _ in 0..=COLS {
+                for _ in 0..=cols {
                     for y in (0..=y).rev() {
-                        let i = y * COLS + x;
+                        let i = y * cols + x;
                         if_free_return_cursor!(i, self.board[i]);
                     }
-                    y = ROWS - 1;
+                    y = rows - 1;
                     if x == 0 {
-                        x = COLS - 1;
+                        x = cols - 1;
                     } else {
                         x -= 1;
                     }
                 }
             }
             Direction::South => {
-                let (mut x, mut y) = if point.y as usize == ROWS - 1 {
-                    if point.x as usize == COLS - 1 {
+                let (mut x, mut y) = if point.y as usize == rows - 1 {
+                    if point.x as usize == cols - 1 {
                         (0, 0)
                     } else {
                         (point.x as usize + 1, 0)
@@ -174,18 +930,18 @@ impl<R> Game<R> {
                 } else {
                     (point.x as usize, point.y as usize + 1)
                 };
-                for _ in 0..=COLS {
-                    let mut i = y * COLS + x;
-                    for _ in y..ROWS {
+                for _ in 0..=cols {
+                    let mut i = y * cols + x;
+                    for _ in y..rows {
                         if_free_return_cursor!(i, self.board[i]);
-                        i += COLS;
+                        i += cols;
                     }
                     y = 0;
-                    x = (x + 1) % COLS;
+                    x = (x + 1) % cols;
                 }
             }
             Direction::East => {
-                let point_i = point.y as usize * COLS + point.x as usize;
+                let point_i = point.y as usize * cols + point.x as usize;
                 let (before, after) = self.board.split_at(point_i);
                 for (i, &v) in after.iter().enumerate().skip(1) {
                     if_free_return_cursor!(point_i + i, v);
@@ -195,7 +951,7 @@ impl<R> Game<R> {
                 }
             }
             Direction::West => {
-                let point_i = point.y as usize * COLS + point.x as usize;
+                let point_i = point.y as usize * cols + point.x as usize;
                 let (before, after) = self.board.split_at(point_i);
                 for (i, &v) in before.iter().enumerate().rev() {
                     if_free_return_cursor!(i, v);
@@ -205,16 +961,12 @@ impl<R> Game<R> {
                 }
             }
         }
-        if self.board[point.y as usize * COLS + point.x as usize].is_some() {
-            Some(point)
-        } else {
-            None
-        }
+        None
     }
 
     /// Loads the board from a textual presentation. Example:
     ///
-    /// ```
+    /// ```text
     /// .........
     /// .1234678.
     /// ...7.0.2.
@@ -226,103 +978,833 @@ impl<R> Game<R> {
     /// .........
     /// ```
     ///
-    /// No guarantee about the state of the game is provided if the
-    /// given content cannot be parsed correctly.
-    #[cfg(feature = "dev")]
+    /// The content must consist of exactly as many lines as the board
+    /// has rows, each with (at least) as many cells as the board has
+    /// columns; free cells are given as '.' or ' ', and only free
+    /// cells may follow the last column.  The game is left
+    /// untouched if the content cannot be parsed correctly.  The undo
+    /// history is dropped.
     pub fn load_from_reader<S: std::io::BufRead>(&mut self, rdr: S) -> anyhow::Result<()> {
-        for (y, line) in rdr.lines().enumerate().take(9) {
-            for (x, c) in line?.bytes().enumerate().take(9) {
-                self.board[y * COLS + x] = if c.is_ascii_digit() {
-                    Some(match c {
-                        b'0' => Stone::_0,
-                        b'1' => Stone::_1,
-                        b'2' => Stone::_2,
-                        b'3' => Stone::_3,
-                        b'4' => Stone::_4,
-                        b'5' => Stone::_5,
-                        b'6' => Stone::_6,
-                        b'7' => Stone::_7,
-                        b'8' => Stone::_8,
-                        b'9' => Stone::_9,
-                        _ => panic!("not an ascii digit: {c:?}"),
-                    })
-                } else if c == b' ' || c == b'.' {
-                    None
-                } else {
-                    anyhow::bail!("invalid character '{c}' [line: {y} / column: {x}");
+        // ~ start out from an empty board; nothing of the current one
+        // survives
+        let mut board = vec![None; self.rows * self.cols];
+        let mut num_lines = 0;
+        for (y, line) in rdr.lines().enumerate() {
+            let line = line?;
+            if y >= self.rows {
+                anyhow::bail!("too many lines; expected {}", self.rows);
+            }
+            let num_cells = line.chars().count();
+            if num_cells < self.cols {
+                anyhow::bail!(
+                    "too few cells [line: {y}]; expected {} but got {num_cells}",
+                    self.cols,
+                );
+            }
+            for (x, c) in line.chars().enumerate() {
+                let cell = match c {
+                    ' ' | '.' => None,
+                    c => match Stone::try_from(c) {
+                        Ok(s) if (s as usize) < self.base => Some(s),
+                        Ok(s) => anyhow::bail!(
+                            "stone '{s}' beyond base {} [line: {y} / column: {x}]",
+                            self.base
+                        ),
+                        Err(_) => {
+                            anyhow::bail!("invalid character '{c}' [line: {y} / column: {x}]")
+                        }
+                    },
                 };
+                // ~ free cells may pad a line; stones must be on the board
+                if x < self.cols {
+                    board[y * self.cols + x] = cell;
+                } else if cell.is_some() {
+                    anyhow::bail!("stone beyond the board [line: {y} / column: {x}]");
+                }
             }
+            num_lines += 1;
         }
+        if num_lines != self.rows {
+            anyhow::bail!("too few lines; expected {} but got {num_lines}", self.rows);
+        }
+        self.visited = visited_of(&board);
+        self.board = board;
         self.num_remaining = self.board.iter().filter(|c| c.is_some()).count();
+        self.par = self.num_placed + par_for(self.num_remaining);
+        self.undo_stack.clear();
+        self.redo_stack.clear();
         Ok(())
     }
 
-    /// Determines whether the game is considered over.
-    pub fn is_finished(&self) -> Option<Finished> {
-        match self.num_remaining {
-            0 => Some(Finished::Success),
-            MAX_STONES => Some(Finished::Failure),
-            _ => None,
+    /// Writes the board in the textual presentation understood by
+    /// `load_from_reader`.
+    pub fn dump_to_writer<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let mut line = vec![b'.'; self.cols + 1];
+        for row in self.board.chunks(self.cols) {
+            for (c, cell) in line.iter_mut().zip(row) {
+                *c = match cell {
+                    Some(s) => s.to_ascii_digit(),
+                    None => b'.',
+                };
+            }
+            line[self.cols] = b'\n';
+            w.write_all(&line)?;
         }
+        Ok(())
     }
-}
 
-impl<R: Rng> Game<R> {
-    pub fn new(mut rng: R) -> Self {
-        Self {
-            board: new_board(&mut rng),
-            nexts: rng.random(),
-            num_placed: 0,
-            num_remaining: (ROWS - 2) * (COLS - 2),
-            rng,
+    /// Encodes the board in a single line, e.g. for sharing it: one
+    /// character per cell row by row; a digit for a stone or '.' for
+    /// a free cell.  With a `seed`, the line starts with the seed and
+    /// the number of placements so far, e.g. "42:7:..1.", as
+    /// understood by `from_compact_string`.
+    pub fn to_compact_string(&self, seed: Option<u64>) -> String {
+        let cells = self.board.iter().map(|cell| match cell {
+            Some(s) => s.to_ascii_digit() as char,
+            None => '.',
+        });
+        match seed {
+            Some(seed) => format!("{seed}:{}:", self.num_placed)
+                .chars()
+                .chain(cells)
+                .collect(),
+            None => cells.collect(),
         }
     }
 
-    pub fn rng(&mut self) -> &mut R {
-        &mut self.rng
+    /// Writes the complete state of the game - apart from its random
+    /// number generator and undo history - such that it can be
+    /// restored by `load_full_from_reader`.
+    pub fn save_to_writer<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        writeln!(w, "{SAVE_HEADER}")?;
+        // ~ the base only if other than the default
+        if self.base == NUM_STONES {
+            writeln!(w, "{} {}", self.rows, self.cols)?;
+        } else {
+            writeln!(w, "{} {} {}", self.rows, self.cols, self.base)?;
+        }
+        writeln!(w, "{}", self.num_placed)?;
+        let nexts: Vec<u8> = self.nexts().map(|s| s.to_ascii_digit()).collect();
+        w.write_all(&nexts)?;
+        writeln!(w)?;
+        self.dump_to_writer(w)?;
+        // ~ the neighbour mode only if other than the default
+        if self.neighbour_mode != NeighbourMode::All {
+            writeln!(w, "neighbours {}", self.neighbour_mode.name())?;
+        }
+        if self.zen {
+            writeln!(w, "zen")?;
+        }
+        if self.num_cleared > 0 {
+            writeln!(w, "cleared {}", self.num_cleared)?;
+        }
+        if let Some(goal) = self.goal_clears {
+            writeln!(w, "goal {goal}")?;
+        }
+        // ~ the size of the magazine only if a finite bag ran short of
+        // filling it
+        if self.nexts().count() < self.magazine.size {
+            writeln!(w, "preview {}", self.magazine.size)?;
+        }
+        // ~ the stones to come if playing with a finite bag; including
+        // those handed back by undone moves
+        if let Some(bag) = self.magazine.bag() {
+            let stones: Vec<u8> = bag.map(|s| s.to_ascii_digit()).collect();
+            w.write_all(b"bag ")?;
+            w.write_all(&stones)?;
+            writeln!(w)?;
+        }
+        // ~ how often each stone was placed and how often it cleared
+        // any, e.g. "stones 3/1 0/0 ..."; once any was placed
+        if self.placed_per_stone.iter().any(|&n| n > 0) {
+            w.write_all(b"stones")?;
+            for (placed, clearing) in self.placed_per_stone.iter().zip(&self.clearing_per_stone) {
+                write!(w, " {placed}/{clearing}")?;
+            }
+            writeln!(w)?;
+        }
+        writeln!(w, "par {}", self.par)
     }
 
-    /// Finds any free place preferrably close to `point`.
-    // ~ panics if `point` is out of the board's bounds
-    pub fn find_free_any(&mut self, point: Cursor) -> Option<Cursor> {
-        if self.num_remaining == MAX_STONES {
-            return None;
+    /// Restores a game written by `save_to_writer`. Unless playing
+    /// with a finite bag, the stones to come after the restored
+    /// "nexts" are drawn from `rng`.
+    pub fn load_full_from_reader<S: std::io::BufRead>(rdr: S, rng: R) -> anyhow::Result<Self> {
+        let mut lines = rdr.lines();
+        let mut next_line = || match lines.next() {
+            Some(line) => Ok(line?),
+            None => Err(anyhow::anyhow!("unexpected end of file")),
+        };
+
+        let header = next_line()?;
+        if header != SAVE_HEADER {
+            anyhow::bail!("unsupported save format: {header:?}");
         }
-        macro_rules! if_free_return_cursor {
-            ($x:expr, $y:expr, $label:literal) => {
-                if self.board[$y as usize * COLS + $x as usize].is_none() {
-                    return Some(Cursor {
-                        x: $x as u8,
-                        y: $y as u8,
-                    });
-                }
-            };
+        // ~ the dimensions of the board optionally followed by the base
+        let line = next_line()?;
+        let (rows, cols, base) = match line.split(' ').collect::<Vec<_>>()[..] {
+            [rows, cols] => (rows.parse::<usize>()?, cols.parse::<usize>()?, NUM_STONES),
+            [rows, cols, base] => (rows.parse()?, cols.parse()?, base.parse()?),
+            _ => anyhow::bail!("invalid board dimensions"),
+        };
+        check_size(rows, cols)?;
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            anyhow::bail!("invalid base: {base}");
         }
-        // ~ is `point` itself free?
-        if_free_return_cursor!(point.x, point.y, "point");
-        // ~ start out in random order to get some variability
-        let directions = {
-            let mut ds = [
-                Direction::North,
-                Direction::East,
-                Direction::South,
-                Direction::West,
-            ];
-            ds.shuffle(&mut self.rng);
-            ds
+        // ~ parses a stone in play
+        let stone = |c: char| Stone::try_from(c).ok().filter(|&s| (s as usize) < base);
+        let num_placed = next_line()?.parse()?;
+        // ~ parses a line of stones
+        let stones = |line: &str| {
+            line.chars()
+                .map(stone)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| anyhow::anyhow!("invalid stones: {line:?}"))
         };
-        // ~ look for free cells in a circle around `point` with an
-        // increasing radius. the number of needed jumps (ups/downs,
-        // lefts/rights) from `point` to a target cell is the distance
-        // which we strive to be minimal in the finally suggested cell
-        let (x, y) = (point.x as usize, point.y as usize);
-        for r in 1..ROWS.max(COLS) {
-            for o in 0..=r {
-                for d in &directions {
-                    match d {
-                        Direction::North => {
+        let line = next_line()?;
+        if line.len() > MAX_NEXTS {
+            anyhow::bail!("invalid nexts: {line:?}");
+        }
+        let nexts = stones(&line)?;
+        let mut board = vec![None; rows * cols];
+        for (y, row) in board.chunks_mut(cols).enumerate() {
+            let line = next_line()?;
+            if line.chars().count() != cols {
+                anyhow::bail!("invalid board line {y}: {line:?}");
+            }
+            for (cell, c) in row.iter_mut().zip(line.chars()) {
+                *cell = match c {
+                    '.' => None,
+                    c => Some(
+                        stone(c)
+                            .ok_or_else(|| anyhow::anyhow!("invalid board line {y}: {line:?}"))?,
+                    ),
+                };
+            }
+        }
+        // ~ optional settings following the board
+        let (mut neighbour_mode, mut bag, mut par) = (NeighbourMode::All, None, None);
+        let mut size = nexts.len();
+        let mut zen = false;
+        let (mut goal_clears, mut num_cleared) = (None, 0);
+        let (mut placed_per_stone, mut clearing_per_stone) = ([0; NUM_STONES], [0; NUM_STONES]);
+        while let Some(line) = lines.next().transpose()? {
+            match line.split_once(' ') {
+                Some(("neighbours", name)) => {
+                    neighbour_mode = NeighbourMode::ALL
+                        .into_iter()
+                        .find(|m| m.name() == name)
+                        .ok_or_else(|| anyhow::anyhow!("invalid neighbour mode: {line:?}"))?;
+                }
+                Some(("bag", line)) => bag = Some(VecDeque::from(stones(line)?)),
+                Some(("par", n)) => par = Some(n.parse()?),
+                Some(("preview", n)) => size = n.parse()?,
+                Some(("goal", n)) => goal_clears = Some(n.parse()?),
+                Some(("cleared", n)) => num_cleared = n.parse()?,
+                Some(("stones", counts)) => {
+                    let counts = counts
+                        .split(' ')
+                        .map(|c| {
+                            let (placed, clearing) = c.split_once('/')?;
+                            Some((placed.parse().ok()?, clearing.parse().ok()?))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .filter(|c| c.len() == NUM_STONES)
+                        .ok_or_else(|| anyhow::anyhow!("invalid stone counts: {line:?}"))?;
+                    for (i, (placed, clearing)) in counts.into_iter().enumerate() {
+                        placed_per_stone[i] = placed;
+                        clearing_per_stone[i] = clearing;
+                    }
+                }
+                None if line == "zen" => zen = true,
+                _ => anyhow::bail!("invalid line: {line:?}"),
+            }
+        }
+        if !(1..=MAX_NEXTS).contains(&size) || nexts.len() > size {
+            anyhow::bail!("invalid magazine size: {size}");
+        }
+        // ~ only a finite bag may run out of stones; then it's empty
+        if nexts.len() < size && bag.as_ref().is_none_or(|b| !b.is_empty()) {
+            anyhow::bail!("invalid nexts: {} stones", nexts.len());
+        }
+        let num_remaining = board.iter().filter(|c| c.is_some()).count();
+        Ok(Self {
+            num_remaining,
+            // ~ saved by earlier versions without a par; estimate it for
+            // the rest of the game
+            par: par.unwrap_or_else(|| num_placed + par_for(num_remaining)),
+            rng,
+            magazine: Magazine {
+                size,
+                nexts,
+                rewound: Vec::new(),
+                source: bag.map_or(Source::Random(base), Source::Bag),
+            },
+            num_placed,
+            rows,
+            cols,
+            base,
+            visited: visited_of(&board),
+            board,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            placed_per_stone,
+            clearing_per_stone,
+            num_cleared,
+            goal_clears,
+            skip_isolated: false,
+            wrap: true,
+            allow_isolated_placement: true,
+            zen,
+            observer: None,
+            neighbour_mode,
+        })
+    }
+
+    // ~ determines the indices of the (up to eight) neighbours of
+    // `point`; `usize::MAX` for those beyond the board or not counting
+    // as neighbours given the game's `neighbour_mode`
+    fn neighbour_indices(&self, point: Cursor) -> [usize; 8] {
+        let (rows, cols) = (self.rows, self.cols);
+        // ~ row above `point`
+        let (x, y) = (point.x as usize, point.y as usize);
+        let mut idxs = [usize::MAX; 8];
+        let i = y * cols + x;
+        if y > 0 {
+            if x > 0 {
+                idxs[0] = i - cols - 1;
+            }
+            idxs[1] = i - cols;
+            if x < (cols - 1) {
+                idxs[2] = i - cols + 1;
+            }
+        }
+        // ~ row of `point`
+        if x > 0 {
+            idxs[3] = i - 1;
+        }
+        if x < (cols - 1) {
+            idxs[4] = i + 1;
+        }
+        // ~ row below `point`
+        if y < (rows - 1) {
+            if x > 0 {
+                idxs[5] = i + cols - 1;
+            }
+            idxs[6] = i + cols;
+            if x < (cols - 1) {
+                idxs[7] = i + cols + 1;
+            }
+        }
+        for &slot in self.neighbour_mode.excluded() {
+            idxs[slot] = usize::MAX;
+        }
+        idxs
+    }
+
+    /// Determines the number of stones around `point` and the last
+    /// digit of their sum in the game's base, i.e.
+    /// `(count, sum % base)`.  The next stone placed onto a free
+    /// `point` clears its neighbours if `count > 0` and the sum
+    /// equals the stone.
+    // ~ panics if `point` is out of bounds
+    pub fn neighbour_sum(&self, point: Cursor) -> (usize, usize) {
+        let (cnt, sum) = self.neighbour_total(point);
+        (cnt, sum % self.base)
+    }
+
+    // ~ the number of stones around `point` and their (full) sum
+    fn neighbour_total(&self, point: Cursor) -> (usize, usize) {
+        self.total_of(&self.neighbour_indices(point))
+    }
+
+    // ~ `neighbour_total` over the already determined `idxs`
+    fn total_of(&self, idxs: &[usize; 8]) -> (usize, usize) {
+        idxs.iter()
+            .filter_map(|&i| if i == usize::MAX { None } else { self.board[i] })
+            .fold((0, 0), |(cnt, sum), v| (cnt + 1, sum + v))
+    }
+
+    /// Determines the cells which would be cleared by placing the next
+    /// stone (from `nexts`) onto the (free) cell at `point`; nothing
+    /// if the placement would just occupy the cell.
+    // ~ panics if `point` is out of bounds
+    pub fn would_clear(&self, point: Cursor) -> impl Iterator<Item = Cursor> + '_ {
+        let (cnt, sum) = self.neighbour_sum(point);
+        let clears = cnt > 0
+            && self
+                .magazine
+                .peek(1)
+                .first()
+                .is_some_and(|&s| s as usize == sum);
+        self.neighbour_indices(point)
+            .into_iter()
+            .filter(move |&i| clears && i != usize::MAX && self.board[i].is_some())
+            .map(|i| Cursor {
+                x: (i % self.cols) as u8,
+                y: (i / self.cols) as u8,
+            })
+    }
+
+    /// Enumerates all free cells of the board in reading order.
+    pub fn free_cells(&self) -> impl Iterator<Item = Cursor> + '_ {
+        self.board
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_none())
+            .map(|(i, _)| Cursor {
+                x: (i % self.cols) as u8,
+                y: (i / self.cols) as u8,
+            })
+    }
+
+    /// Finds a free place where the next stone (from `nexts`) would
+    /// clear its neighbours; `None` if there is no next stone.
+    pub fn find_hint(&self) -> Option<Cursor> {
+        let next = *self.magazine.peek(1).first()? as usize;
+        self.free_cells().find(|&point| {
+            let (cnt, sum) = self.neighbour_sum(point);
+            cnt > 0 && sum == next
+        })
+    }
+
+    /// Determines whether the game is considered over; with a finite
+    /// bag, the game is lost once it and the magazine ran out of
+    /// stones without clearing the board.  In zen mode (see
+    /// `set_zen`) a deadlocked board isn't the end of the game unless
+    /// there is no free cell left at all.
+    pub fn is_finished(&self) -> Option<Finished> {
+        // ~ only a full board is beyond the help of zen mode, e.g. if
+        // loaded as such
+        let stuck =
+            || self.is_deadlocked() && (!self.zen || self.num_remaining == self.board.len());
+        match self.num_remaining {
+            0 => Some(Finished::Success),
+            _ if self
+                .goal_clears
+                .is_some_and(|goal| self.num_cleared >= goal) =>
+            {
+                Some(Finished::Success)
+            }
+            _ if self.magazine.peek(1).is_empty() || stuck() => Some(Finished::Failure),
+            _ => None,
+        }
+    }
+
+    /// Determines whether no placement can ever clear stones again
+    /// such that the number of stones on the board could only grow;
+    /// i.e. whether stones remain but no free cell has any of them as
+    /// a neighbour.  That's the case for a full board and, with
+    /// diagonal neighbours only, for a board one of whose two
+    /// checkered halves is full while the other is empty.  A finite
+    /// bag lacking the stones the free cells ask for is not detected
+    /// here; the game ends once the bag runs out instead.
+    // ~ any stone may still come, hence a free cell next to a stone
+    // always retains the chance of clearing it. with the cells of the
+    // board being connected, there's always such a free cell unless
+    // the board is full (or empty); no need to scan the board but
+    // for diagonal neighbours, which don't connect the two halves.
+    pub fn is_deadlocked(&self) -> bool {
+        match self.num_remaining {
+            0 => false,
+            n if n == self.board.len() => true,
+            _ => {
+                self.neighbour_mode == NeighbourMode::Diagonal
+                    && self
+                        .free_cells()
+                        .all(|point| self.neighbour_sum(point).0 == 0)
+            }
+        }
+    }
+}
+
+impl<R: Rng> Game<R> {
+    /// Captures the state of the game as plain data; see
+    /// `GameSnapshot`.  The snapshot's `seed` is left to the caller.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            seed: None,
+            rows: self.rows,
+            cols: self.cols,
+            base: self.base,
+            neighbour_mode: self.neighbour_mode,
+            board: self.board.clone(),
+            num_nexts: self.magazine.size,
+            nexts: self.nexts().collect(),
+            bag: self.magazine.bag().map(Iterator::collect),
+            num_placed: self.num_placed,
+            par: self.par,
+            num_cleared: self.num_cleared,
+            placed_per_stone: self.placed_per_stone,
+            clearing_per_stone: self.clearing_per_stone,
+        }
+    }
+
+    /// Restores a game captured by `snapshot`.  The random number
+    /// generator can't be captured; unless playing with a finite bag,
+    /// the stones to come after the restored "nexts" are drawn from
+    /// `rng`, e.g. one created anew from the snapshot's `seed`.  Fails
+    /// if the snapshot isn't consistent, e.g. after being edited.
+    pub fn from_snapshot(snapshot: &GameSnapshot, rng: R) -> anyhow::Result<Self> {
+        let GameSnapshot {
+            rows, cols, base, ..
+        } = *snapshot;
+        check_size(rows, cols)?;
+        if !(MIN_BASE..=MAX_BASE).contains(&base) {
+            anyhow::bail!("invalid base: {base}");
+        }
+        if snapshot.board.len() != rows * cols {
+            anyhow::bail!(
+                "invalid number of cells: {} (expected {rows}x{cols})",
+                snapshot.board.len()
+            );
+        }
+        // ~ only the stones in play
+        let in_play = |s: &Stone| (*s as usize) < base;
+        if !snapshot.board.iter().flatten().all(in_play) {
+            anyhow::bail!("invalid board: stones beyond the base {base}");
+        }
+        let bag = snapshot.bag.as_deref();
+        if !snapshot
+            .nexts
+            .iter()
+            .chain(bag.into_iter().flatten())
+            .all(in_play)
+        {
+            anyhow::bail!("invalid nexts: stones beyond the base {base}");
+        }
+        if !(1..=MAX_NEXTS).contains(&snapshot.num_nexts) {
+            anyhow::bail!("invalid magazine size: {}", snapshot.num_nexts);
+        }
+        // ~ only a finite bag may run out of stones; then it's empty
+        if snapshot.nexts.len() > snapshot.num_nexts
+            || (snapshot.nexts.len() < snapshot.num_nexts && bag.is_none_or(|b| !b.is_empty()))
+        {
+            anyhow::bail!("invalid nexts: {} stones", snapshot.nexts.len());
+        }
+        Ok(Self {
+            rng,
+            magazine: Magazine {
+                size: snapshot.num_nexts,
+                nexts: snapshot.nexts.clone(),
+                rewound: Vec::new(),
+                source: bag.map_or(Source::Random(base), |bag| {
+                    Source::Bag(bag.iter().copied().collect())
+                }),
+            },
+            num_remaining: snapshot.board.iter().filter(|c| c.is_some()).count(),
+            num_placed: snapshot.num_placed,
+            par: snapshot.par,
+            rows,
+            cols,
+            base,
+            board: snapshot.board.clone(),
+            visited: visited_of(&snapshot.board),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            placed_per_stone: snapshot.placed_per_stone,
+            clearing_per_stone: snapshot.clearing_per_stone,
+            num_cleared: snapshot.num_cleared,
+            goal_clears: None,
+            skip_isolated: false,
+            wrap: true,
+            allow_isolated_placement: true,
+            zen: false,
+            observer: None,
+            neighbour_mode: snapshot.neighbour_mode,
+        })
+    }
+
+    /// Creates a new classic game: a board of the default size
+    /// (`ROWS` x `COLS`) filled at the default difficulty, previewing
+    /// `NUM_NEXTS` stones to come, played in base ten.
+    pub fn new(rng: R) -> Self {
+        Self::with_size(ROWS, COLS, rng)
+    }
+
+    /// Creates a new classic game (see `new`) on a board of the given
+    /// size.
+    // ~ panics if `rows` or `cols` is not within `MIN_SIZE..=MAX_SIZE`
+    pub fn with_size(rows: usize, cols: usize, rng: R) -> Self {
+        Self::new_with_base(rows, cols, Difficulty::default(), NUM_NEXTS, MAX_BASE, rng)
+    }
+
+    /// Creates a new game on a board of the given size filled
+    /// according to `difficulty`, previewing `num_nexts` stones to
+    /// come.  The game is played in the given `base`: the neighbours'
+    /// sum is taken modulo `base` and only the stones `0..base` are in
+    /// play; the classic game is played in base ten (`MAX_BASE`), i.e.
+    /// with all the stones.
+    // ~ panics if `rows` or `cols` is not within `MIN_SIZE..=MAX_SIZE`,
+    // if `num_nexts` is not within `1..=MAX_NEXTS`, or if `base` is
+    // not within `MIN_BASE..=MAX_BASE`
+    pub fn new_with_base(
+        rows: usize,
+        cols: usize,
+        difficulty: Difficulty,
+        num_nexts: usize,
+        base: usize,
+        mut rng: R,
+    ) -> Self {
+        assert!(
+            (MIN_SIZE..=MAX_SIZE).contains(&rows) && (MIN_SIZE..=MAX_SIZE).contains(&cols),
+            "invalid board size: {rows}x{cols}"
+        );
+        assert!(
+            (1..=MAX_NEXTS).contains(&num_nexts),
+            "invalid magazine size: {num_nexts}"
+        );
+        assert!(
+            (MIN_BASE..=MAX_BASE).contains(&base),
+            "invalid base: {base}"
+        );
+        let board = new_board(rows, cols, difficulty, base, &mut rng);
+        Self::with_board(rows, cols, board, num_nexts, base, rng)
+    }
+
+    /// Creates a new game on a board of the given size with the given
+    /// `cells` (row by row) previewing `NUM_NEXTS` stones to come; the
+    /// classic game played in base ten.  Fails if `cells` doesn't
+    /// match the board's size.
+    // ~ for setting up specific positions (e.g. in tests) without
+    // going through the textual representation of a board
+    pub fn from_cells(
+        rng: R,
+        rows: usize,
+        cols: usize,
+        cells: &[Option<Stone>],
+    ) -> anyhow::Result<Self> {
+        check_size(rows, cols)?;
+        if cells.len() != rows * cols {
+            anyhow::bail!(
+                "invalid number of cells: {} (expected {rows}x{cols})",
+                cells.len()
+            );
+        }
+        Ok(Self::with_board(
+            rows,
+            cols,
+            cells.to_vec(),
+            NUM_NEXTS,
+            MAX_BASE,
+            rng,
+        ))
+    }
+
+    /// Creates a new game on a board of the given size from its
+    /// encoding by `to_compact_string`; returning the seed it was
+    /// prefixed with, if any.  Fails unless the encoding has exactly
+    /// one character per cell of the board.
+    pub fn from_compact_string(
+        s: &str,
+        rows: usize,
+        cols: usize,
+        rng: R,
+    ) -> anyhow::Result<(Self, Option<u64>)> {
+        let (seed, num_placed, line) = match s.trim().split(':').collect::<Vec<_>>()[..] {
+            [line] => (None, 0, line),
+            [seed, num_placed, line] => (Some(seed.parse()?), num_placed.parse()?, line),
+            _ => anyhow::bail!("invalid compact board: {s:?}"),
+        };
+        if line.chars().count() != rows * cols {
+            anyhow::bail!(
+                "invalid compact board: {} cells (expected {rows}x{cols})",
+                line.chars().count()
+            );
+        }
+        let cells = line
+            .chars()
+            .map(|c| match c {
+                '.' => Ok(None),
+                c => Stone::try_from(c)
+                    .map(Some)
+                    .map_err(|_| anyhow::anyhow!("invalid cell in compact board: {c:?}")),
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut game = Self::from_cells(rng, rows, cols, &cells)?;
+        game.num_placed = num_placed;
+        game.par = num_placed + par_for(game.num_remaining);
+        Ok((game, seed))
+    }
+
+    // ~ sets up a new game around the given (valid) `board`
+    fn with_board(
+        rows: usize,
+        cols: usize,
+        board: Vec<Option<Stone>>,
+        num_nexts: usize,
+        base: usize,
+        mut rng: R,
+    ) -> Self {
+        let num_remaining = board.iter().filter(|c| c.is_some()).count();
+        Self {
+            num_remaining,
+            visited: visited_of(&board),
+            board,
+            magazine: Magazine::new(num_nexts, base, &mut rng),
+            num_placed: 0,
+            par: par_for(num_remaining),
+            rows,
+            cols,
+            base,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            placed_per_stone: [0; NUM_STONES],
+            clearing_per_stone: [0; NUM_STONES],
+            num_cleared: 0,
+            goal_clears: None,
+            skip_isolated: false,
+            wrap: true,
+            allow_isolated_placement: true,
+            zen: false,
+            observer: None,
+            neighbour_mode: NeighbourMode::All,
+            rng,
+        }
+    }
+
+    /// Starts over with a new board of the same size filled according
+    /// to `difficulty` and the stones drawn from `rng`, which replaces
+    /// the game's current one; the very same game as created by
+    /// `new_with_base` given the same arguments.  The size of the
+    /// magazine, the base, the neighbour mode, and the cursor
+    /// preferences are retained; a finite bag is not.
+    pub fn reinit_with_rng(&mut self, difficulty: Difficulty, mut rng: R) {
+        self.board = new_board(self.rows, self.cols, difficulty, self.base, &mut rng);
+        self.visited = visited_of(&self.board);
+        self.num_remaining = self.board.iter().filter(|c| c.is_some()).count();
+        self.magazine = Magazine::new(self.magazine.size, self.base, &mut rng);
+        self.num_placed = 0;
+        self.par = par_for(self.num_remaining);
+        self.placed_per_stone = [0; NUM_STONES];
+        self.clearing_per_stone = [0; NUM_STONES];
+        self.num_cleared = 0;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.rng = rng;
+    }
+
+    pub fn rng(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Picks a free cell away from the board's edges for the cursor
+    /// to start at; drawn from the game's random number generator,
+    /// hence the same for the same seed.  Falls back to any free cell
+    /// (and the top-left corner if there is none).
+    pub fn initial_cursor(&mut self) -> Cursor {
+        let (rows, cols) = (self.rows as u8, self.cols as u8);
+        let interior: Vec<_> = self
+            .free_cells()
+            .filter(|p| (1..cols - 1).contains(&p.x) && (1..rows - 1).contains(&p.y))
+            .collect();
+        let cells = if interior.is_empty() {
+            self.free_cells().collect()
+        } else {
+            interior
+        };
+        cells.choose(&mut self.rng).copied().unwrap_or_default()
+    }
+
+    /// Plays with a finite bag instead of an endless stream of
+    /// stones: the bag holds `counts[s]` of each stone `s` in play,
+    /// shuffled by the game's random number generator, and refills
+    /// the magazine until it runs out.  The stones previewed so far
+    /// are dropped in favour of those drawn from the bag.  `counts`
+    /// must not list more stones than are in play, i.e. than the
+    /// game's base; the game is left untouched otherwise.
+    pub fn set_finite_bag(&mut self, counts: &[usize]) -> anyhow::Result<()> {
+        if counts.len() > self.base {
+            anyhow::bail!(
+                "counts for {} stones but only {} in play",
+                counts.len(),
+                self.base
+            );
+        }
+        let mut stones: Vec<Stone> = (0..NUM_STONES as u8)
+            .filter_map(|n| Stone::try_from(n).ok())
+            .zip(counts)
+            .flat_map(|(s, &n)| std::iter::repeat_n(s, n))
+            .collect();
+        stones.shuffle(&mut self.rng);
+        self.magazine = Magazine::with_bag(self.magazine.size, VecDeque::from(stones));
+        Ok(())
+    }
+
+    /// Finds any free place preferrably close to `point`; which may be
+    /// out of the board's bounds.
+    pub fn find_free_any(&self, point: Cursor) -> Option<Cursor> {
+        let point = self.clamp(point);
+        self.skip_isolated
+            .then(|| self.find_free_any_by(point, |p| !self.is_isolated(p)))
+            .flatten()
+            .or_else(|| self.find_free_any_by(point, |_| true))
+    }
+
+    /// Finds the free place nearest to `point` by the Chebyshev
+    /// distance, i.e. the number of king's moves on a chess board
+    /// (without wrapping around the board's edges); `point` itself if
+    /// it's free.  Unlike `find_free_any`, ties are broken
+    /// deterministically: the first place in reading order (top to
+    /// bottom, left to right) wins.  A `point` out of the board's
+    /// bounds is taken for the closest cell on the board.
+    pub fn find_free_nearest(&self, point: Cursor) -> Option<Cursor> {
+        let point = self.clamp(point);
+        // ~ `min_by_key` retains the first of equally distant cells
+        self.free_cells()
+            .min_by_key(|p| p.x.abs_diff(point.x).max(p.y.abs_diff(point.y)))
+    }
+
+    /// Finds the free place closest to `point` - in the sense of
+    /// `find_free_any` - where the next stone (from `nexts`) would
+    /// clear its neighbours.
+    // ~ panics if `point` is out of the board's bounds
+    pub fn find_nearest_clearing(&self, point: Cursor) -> Option<Cursor> {
+        self.find_free_any_by(point, |p| self.would_clear(p).next().is_some())
+    }
+
+    // ~ does the work of `find_free_any` considering only the free
+    // cells accepted by `accept`
+    fn find_free_any_by(&self, point: Cursor, accept: impl Fn(Cursor) -> bool) -> Option<Cursor> {
+        if self.num_remaining == self.board.len() {
+            return None;
+        }
+        let (rows, cols) = (self.rows, self.cols);
+        macro_rules! if_free_return_cursor {
+            ($x:expr, $y:expr, $label:literal) => {
+                if self.board[$y as usize * cols + $x as usize].is_none() {
+                    let cursor = Cursor {
+                        x: $x as u8,
+                        y: $y as u8,
+                    };
+                    if accept(cursor) {
+                        return Some(cursor);
+                    }
+                }
+            };
+        }
+        // ~ is `point` itself free?
+        if_free_return_cursor!(point.x, point.y, "point");
+        // ~ start out in varying order to get some variability; not
+        // drawn from `rng` as that would alter the stones to come
+        // depending on the moves of the cursor
+        let directions = {
+            let mut ds = Direction::all();
+            ds.rotate_left(self.num_placed % 4);
+            ds
+        };
+        // ~ look for free cells in a circle around `point` with an
+        // increasing radius. the number of needed jumps (ups/downs,
+        // lefts/rights) from `point` to a target cell is the distance
+        // which we strive to be minimal in the finally suggested cell
+        let (x, y) = (point.x as usize, point.y as usize);
+        for r in 1..rows.max(cols) {
+            for o in 0..=r {
+                for d in directions {
+                    match d {
+                        Direction::North => {
                             if y >= r {
-                                if x + o < COLS {
+                                if x + o < cols {
                                     if_free_return_cursor!(x + o, y - r, "north right");
                                 }
                                 if o > 0 && x >= o {
@@ -332,8 +1814,8 @@ impl<R: Rng> Game<R> {
                         }
                         Direction::East => {
                             // ~ corners are checked by "north" and "south"
-                            if o != r && x + r < COLS {
-                                if y + o < ROWS {
+                            if o != r && x + r < cols {
+                                if y + o < rows {
                                     if_free_return_cursor!(x + r, y + o, "east  down");
                                 }
                                 if o > 0 && y >= o {
@@ -342,11 +1824,11 @@ impl<R: Rng> Game<R> {
                             }
                         }
                         Direction::South => {
-                            if y + r < ROWS {
+                            if y + r < rows {
                                 if x >= o {
                                     if_free_return_cursor!(x - o, y + r, "south left");
                                 }
-                                if o > 0 && x + o < COLS {
+                                if o > 0 && x + o < cols {
                                     if_free_return_cursor!(x + o, y + r, "south right");
                                 }
                             }
@@ -357,7 +1839,7 @@ impl<R: Rng> Game<R> {
                                 if y >= o {
                                     if_free_return_cursor!(x - r, y - o, "west  up");
                                 }
-                                if o > 0 && y + o < ROWS {
+                                if o > 0 && y + o < rows {
                                     if_free_return_cursor!(x - r, y + o, "west  down");
                                 }
                             }
@@ -369,79 +1851,210 @@ impl<R: Rng> Game<R> {
         None
     }
 
-    /// Assuming the cell at `point` is free, attempt to place the
-    /// next stone (from `nexts`) to it, returning `true` if the stone
-    /// was placed and now occupies the cell, or `false` if it cleared
-    /// all neighbours and the cell at `point` was left free.
-    // ~ panics if `point` is out of bounds
-    pub fn place_next(&mut self, point: Cursor) -> bool {
-        let (idxs, cnt, sum) = {
-            // ~ row above `point`
-            let (x, y) = (point.x as usize, point.y as usize);
-            let mut idxs = [usize::MAX; 8];
-            let i = y * COLS + x;
-            if y > 0 {
-                if x > 0 {
-                    idxs[0] = i - COLS - 1;
-                }
-                idxs[1] = i - COLS;
-                if x < (COLS - 1) {
-                    idxs[2] = i - COLS + 1;
-                }
-            }
-            // ~ row of `point`
-            if x > 0 {
-                idxs[3] = i - 1;
-            }
-            if x < (COLS - 1) {
-                idxs[4] = i + 1;
+    /// Attempts to place the next stone (from `nexts`) to the cell at
+    /// `point`.  Unless isolated placements are allowed (see
+    /// `set_allow_isolated_placement`), the game is left untouched if
+    /// there are no stones around `point`; as it is if `point` is out
+    /// of the board's bounds or the cell is occupied already.
+    // ~ panics if a finite bag and the magazine ran out of stones,
+    // i.e. the game is over
+    pub fn place_next(&mut self, point: Cursor) -> PlaceOutcome {
+        if self.try_get(point.y as usize, point.x as usize) != Some(None) {
+            return PlaceOutcome::Refused;
+        }
+        if !self.allow_isolated_placement && self.is_isolated(point) {
+            return PlaceOutcome::Refused;
+        }
+        self.redo_stack.clear();
+        let outcome = self.place(point);
+        if let Some(mut observer) = self.observer.take() {
+            match &outcome {
+                PlaceOutcome::Occupied(p) => observer.on_event(&GameEvent::Placed(point, p)),
+                PlaceOutcome::Cleared(p) => observer.on_event(&GameEvent::Cleared(point, p)),
+                PlaceOutcome::Refused => {}
             }
-            // ~ row below `point`
-            if y < (ROWS - 1) {
-                if x > 0 {
-                    idxs[5] = i + COLS - 1;
-                }
-                idxs[6] = i + COLS;
-                if x < (COLS - 1) {
-                    idxs[7] = i + COLS + 1;
-                }
+            if let Some(finished) = self.is_finished() {
+                observer.on_event(&GameEvent::Finished(finished));
             }
+            self.observer = Some(observer);
+        }
+        outcome
+    }
 
-            let (cnt, sum) = idxs
-                .iter()
-                .filter_map(|&i| if i == usize::MAX { None } else { self.board[i] })
-                .fold((0, 0), |(cnt, sum), v| (cnt + 1, sum + v));
-            (idxs, cnt, (sum % NUM_STONES))
-        };
+    // ~ does the work of `place_next` leaving the redo stack untouched
+    fn place(&mut self, point: Cursor) -> PlaceOutcome {
+        let idxs = self.neighbour_indices(point);
+        let (cnt, total) = self.total_of(&idxs);
+        let sum = total % self.base;
 
-        let next = self.nexts[0];
-        for i in 0..(NUM_NEXTS - 1) {
-            self.nexts[i] = self.nexts[i + 1];
-        }
-        self.nexts[NUM_NEXTS - 1] = self.rng.random();
+        let next = self
+            .magazine
+            .advance(&mut self.rng)
+            .expect("magazine ran out of stones");
+        let mut m = Move {
+            point,
+            cells: [(usize::MAX, None); 9],
+            next,
+            visited: false,
+            cleared: false,
+            num_remaining: self.num_remaining,
+            num_placed: self.num_placed,
+            num_cleared: self.num_cleared,
+        };
+        let mut placement = Placement {
+            stone: next,
+            num_neighbours: cnt,
+            sum: total,
+            cleared: Vec::new(),
+        };
 
-        let cleared = if cnt > 0 && next as usize == sum {
-            idxs.into_iter().filter(|&i| i != usize::MAX).for_each(|i| {
-                self.board[i] = None;
-            });
-            self.num_remaining -= cnt;
+        let occupied = if cnt > 0 && next as usize == sum {
+            self.clear_cells(idxs, &mut m.cells[..8], &mut placement.cleared);
             false
         } else {
-            self.board[point.y as usize * COLS + point.x as usize] = Some(next);
+            let i = point.y as usize * self.cols + point.x as usize;
+            m.cells[0] = (i, self.board[i]);
+            self.board[i] = Some(next);
             self.num_remaining += 1;
-            true
+            // ~ rather than deadlocking the board, the stone clears itself
+            // along with its neighbours
+            if self.zen && self.is_deadlocked() {
+                self.board[i] = None;
+                self.num_remaining -= 1;
+                self.clear_cells(idxs, &mut m.cells[1..], &mut placement.cleared);
+                false
+            } else {
+                true
+            }
         };
         self.num_placed = self.num_placed.saturating_add(1);
-        cleared
+        self.placed_per_stone[next as usize] += 1;
+        if !occupied {
+            self.clearing_per_stone[next as usize] += 1;
+        }
+        m.cleared = !occupied;
+        if occupied {
+            let i = point.y as usize * self.cols + point.x as usize;
+            m.visited = std::mem::replace(&mut self.visited[i], true);
+        }
+        self.num_cleared += placement.cleared.len();
+        if self.undo_stack.len() == MAX_UNDO {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(m);
+        if occupied {
+            PlaceOutcome::Occupied(placement)
+        } else {
+            PlaceOutcome::Cleared(placement)
+        }
+    }
+
+    // ~ clears the cells at `idxs` (skipping `usize::MAX`) recording
+    // their previous state in `cells` and the cleared stones in
+    // `cleared`
+    fn clear_cells(
+        &mut self,
+        idxs: [usize; 8],
+        cells: &mut [(usize, Option<Stone>)],
+        cleared: &mut Vec<(Cursor, Stone)>,
+    ) {
+        for (slot, i) in idxs.into_iter().enumerate() {
+            if i != usize::MAX {
+                cells[slot] = (i, self.board[i]);
+                if let Some(s) = self.board[i].take() {
+                    let x = (i % self.cols) as u8;
+                    let y = (i / self.cols) as u8;
+                    cleared.push((Cursor { x, y }, s));
+                    // ~ the cleared cells are stones on the board
+                    debug_assert!(self.num_remaining > 0, "clearing {i} of no stones");
+                    self.num_remaining = self.num_remaining.saturating_sub(1);
+                }
+            }
+        }
+    }
+
+    /// Reverts the most recent `place_next`, returning the cell the
+    /// undone stone was placed onto, or `None` if there's nothing to
+    /// undo.
+    pub fn undo(&mut self) -> Option<Cursor> {
+        let m = self.undo_stack.pop()?;
+        for (i, v) in m.cells {
+            if i != usize::MAX {
+                self.board[i] = v;
+            }
+        }
+        if !m.cleared {
+            let i = m.point.y as usize * self.cols + m.point.x as usize;
+            self.visited[i] = m.visited;
+        }
+        self.magazine.rewind(m.next);
+        self.num_remaining = m.num_remaining;
+        self.num_placed = m.num_placed;
+        self.num_cleared = m.num_cleared;
+        self.placed_per_stone[m.next as usize] -= 1;
+        if m.cleared {
+            self.clearing_per_stone[m.next as usize] -= 1;
+        }
+        let point = m.point;
+        self.redo_stack.push(m);
+        Some(point)
+    }
+
+    /// Re-applies the most recently undone placement, returning the
+    /// cell the stone was placed onto, or `None` if there's nothing to
+    /// redo.
+    pub fn redo(&mut self) -> Option<Cursor> {
+        let m = self.redo_stack.pop()?;
+        // ~ the stone stream was rewound by `undo`, hence placing
+        // again yields exactly the undone move
+        self.place(m.point);
+        Some(m.point)
     }
 }
 
-fn new_board<R: Rng>(rng: &mut R) -> [Option<Stone>; MAX_STONES] {
-    let mut xs = [None::<Stone>; MAX_STONES];
+// ~ the cells occupied on `board`; the initial `Game::visited`
+fn visited_of(board: &[Option<Stone>]) -> Vec<bool> {
+    board.iter().map(Option::is_some).collect()
+}
+
+fn new_board<R: Rng>(
+    rows: usize,
+    cols: usize,
+    difficulty: Difficulty,
+    base: usize,
+    rng: &mut R,
+) -> Vec<Option<Stone>> {
+    let mut xs = vec![None::<Stone>; rows * cols];
     // ~ middle cells
-    for row in 1..(ROWS - 1) {
-        for col in 1..COLS - 1 {
-            xs[row * COLS + col] = Some(rng.random::<Stone>());
+    for row in 1..(rows - 1) {
+        for col in 1..cols - 1 {
+            xs[row * cols + col] = Some(rng.sample(Stones(base)));
+        }
+    }
+    // ~ note: the "normal" board must not consume any further
+    // randomness to keep seeds stable
+    match difficulty {
+        Difficulty::Easy => {
+            // ~ clear a quarter of the middle cells (on average)
+            for row in 1..(rows - 1) {
+                for col in 1..cols - 1 {
+                    if rng.next_u32().is_multiple_of(4) {
+                        xs[row * cols + col] = None;
+                    }
+                }
+            }
+        }
+        Difficulty::Normal => {}
+        Difficulty::Hard => {
+            // ~ fill half of the border cells (on average)
+            for (i, x) in xs.iter_mut().enumerate() {
+                let (row, col) = (i / cols, i % cols);
+                if (row == 0 || row == rows - 1 || col == 0 || col == cols - 1)
+                    && rng.next_u32().is_multiple_of(2)
+                {
+                    *x = Some(rng.sample(Stones(base)));
+                }
+            }
         }
     }
     xs
@@ -449,7 +2062,15 @@ fn new_board<R: Rng>(rng: &mut R) -> [Option<Stone>; MAX_STONES] {
 
 #[cfg(test)]
 mod tests {
-    use super::{COLS, Cursor, Game, ROWS, Stone};
+    use std::{cell::RefCell, rc::Rc};
+
+    use rand::SeedableRng;
+
+    use super::{
+        COLS, Cursor, Difficulty, Direction, Finished, Game, GameEvent, InvalidStone, MAX_BASE,
+        MAX_SIZE, MAX_UNDO, NUM_NEXTS, NeighbourMode, PlaceOutcome, Placement, ROWS, Stone,
+    };
+    use crate::rng::Pcg32;
 
     #[test]
     fn assert_stone_size() {
@@ -475,56 +2096,1580 @@ mod tests {
         }
     }
 
-    impl TryFrom<char> for Stone {
-        type Error = &'static str;
+    fn make_board(board: [&str; ROWS]) -> Game<ConstantRng> {
+        let cells: Vec<_> = board
+            .iter()
+            .flat_map(|line| line.chars().map(|c| c.try_into().ok()))
+            .collect();
+        Game::from_cells(ConstantRng, ROWS, COLS, &cells).unwrap()
+    }
 
-        fn try_from(value: char) -> Result<Self, Self::Error> {
-            match value {
-                '0' => Ok(Stone::_0),
-                '1' => Ok(Stone::_1),
-                '2' => Ok(Stone::_2),
-                '3' => Ok(Stone::_3),
-                '4' => Ok(Stone::_4),
-                '5' => Ok(Stone::_5),
-                '6' => Ok(Stone::_6),
-                '7' => Ok(Stone::_7),
-                '8' => Ok(Stone::_8),
-                '9' => Ok(Stone::_9),
-                _ => Err("Not an ASCII digit"),
-            }
+    fn board_of<R>(game: &Game<R>) -> Vec<Option<Stone>> {
+        game.board.clone()
+    }
+
+    #[test]
+    fn test_stone_conversions() {
+        for n in 0..10u8 {
+            let s = Stone::try_from(n).unwrap();
+            assert_eq!(n, s as u8);
+            let digit = char::from(b'0' + n);
+            assert_eq!(Ok(s), Stone::try_from(digit));
+            assert_eq!(Ok(s), digit.to_string().parse());
+            assert_eq!(digit.to_string(), s.to_string());
         }
+        assert_eq!(Err(InvalidStone), Stone::try_from(10u8));
+        assert_eq!(Err(InvalidStone), Stone::try_from('x'));
+        assert_eq!(Err(InvalidStone), Stone::try_from('٣'));
+        for s in ["", "12", "x", " 1"] {
+            assert_eq!(Err(InvalidStone), s.parse::<Stone>());
+        }
+        assert_eq!("[ 7]", format!("[{:>2}]", Stone::_7));
     }
 
-    fn make_board(board: [&str; ROWS]) -> Game<ConstantRng> {
+    #[test]
+    fn test_undo() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..9......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        assert_eq!(None, game.undo());
+
+        // ~ occupying placement; sum(1) != 0
+        let board = board_of(&game);
+        assert_eq!(
+            PlaceOutcome::Occupied(Placement {
+                stone: Stone::_0,
+                num_neighbours: 1,
+                sum: 1,
+                cleared: vec![],
+            }),
+            game.place_next(Cursor { x: 0, y: 0 })
+        );
+        assert_eq!(Some(Stone::_0), game.get(0, 0));
+        assert_eq!(Some(Cursor { x: 0, y: 0 }), game.undo());
+        assert_eq!(board, board_of(&game));
+        assert_eq!((2, 0), (game.num_remaining, game.num_placed));
+        assert_eq!(None, game.undo());
+
+        // ~ clearing placement; (1 + 9) % 10 == 0
+        assert_eq!(
+            PlaceOutcome::Cleared(Placement {
+                stone: Stone::_0,
+                num_neighbours: 2,
+                sum: 10,
+                cleared: vec![
+                    (Cursor { x: 1, y: 1 }, Stone::_1),
+                    (Cursor { x: 2, y: 2 }, Stone::_9)
+                ],
+            }),
+            game.place_next(Cursor { x: 2, y: 1 })
+        );
+        assert_eq!((None, None), (game.get(1, 1), game.get(2, 2)));
+        assert_eq!(Some(Cursor { x: 2, y: 1 }), game.undo());
+        assert_eq!(board, board_of(&game));
+        assert_eq!((2, 0), (game.num_remaining, game.num_placed));
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..9......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        let board_0 = board_of(&game);
+        game.place_next(Cursor { x: 5, y: 5 });
+        let board_1 = board_of(&game);
+        game.place_next(Cursor { x: 2, y: 1 });
+        let board_2 = board_of(&game);
+        assert_eq!(None, game.redo());
+
+        assert_eq!(Some(Cursor { x: 2, y: 1 }), game.undo());
+        assert_eq!(board_1, board_of(&game));
+        assert_eq!(Some(Cursor { x: 5, y: 5 }), game.undo());
+        assert_eq!(board_0, board_of(&game));
+        assert_eq!(None, game.undo());
+
+        assert_eq!(Some(Cursor { x: 5, y: 5 }), game.redo());
+        assert_eq!(board_1, board_of(&game));
+        assert_eq!(Some(Cursor { x: 2, y: 1 }), game.redo());
+        assert_eq!(board_2, board_of(&game));
+        assert_eq!((1, 2), (game.num_remaining, game.num_placed));
+        assert_eq!(None, game.redo());
+
+        // ~ a new placement discards the redo history
+        game.undo();
+        game.place_next(Cursor { x: 0, y: 8 });
+        assert_eq!(None, game.redo());
+    }
+
+    #[test]
+    fn test_undo_bounded() {
+        let mut game = make_board(["........."; ROWS]);
+        game.num_remaining = 0;
+        // ~ a zero placed and cleared by the next one alternately
+        for i in 0..(MAX_UNDO + 10) {
+            let point = Cursor {
+                x: 4,
+                y: 4 + (i % 2) as u8,
+            };
+            assert_ne!(PlaceOutcome::Refused, game.place_next(point));
+        }
+        let mut n = 0;
+        while game.undo().is_some() {
+            n += 1;
+        }
+        assert_eq!(MAX_UNDO, n);
+    }
+
+    #[test]
+    fn test_dump_load_roundtrip() {
+        let board = "\
+.........
+.1234678.
+...7.0.2.
+.1234678.
+.123.679.
+.1...638.
+.12.4670.
+.1234678.
+.........
+";
         let mut game = Game::new(ConstantRng);
-        for (y, line) in board.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                game.board[y * COLS + x] = c.try_into().ok();
-            }
+        game.load_from_reader(board.as_bytes()).unwrap();
+        let mut dumped = Vec::new();
+        game.dump_to_writer(&mut dumped).unwrap();
+        assert_eq!(board.as_bytes(), dumped);
+        assert_eq!(40, game.num_remaining);
+
+        // ~ incomplete boards are rejected leaving the game untouched
+        let (short, _) = board.split_at(board.len() - 10);
+        assert!(game.load_from_reader(short.as_bytes()).is_err());
+        let narrow = board.replace(".1234678.", ".123");
+        assert!(game.load_from_reader(narrow.as_bytes()).is_err());
+        let long = format!("{board}.........\n");
+        assert!(game.load_from_reader(long.as_bytes()).is_err());
+        // ~ cells are characters, not bytes; and nothing but cells
+        let wide = board.replace(".1234678.", ".12346７8");
+        assert!(game.load_from_reader(wide.as_bytes()).is_err());
+        let beyond = board.replace(".1234678.", ".1234678.5");
+        assert!(game.load_from_reader(beyond.as_bytes()).is_err());
+        let padded = board.replace(".1234678.", ".1234678.. ");
+        assert!(game.load_from_reader(padded.as_bytes()).is_ok());
+        game.load_from_reader(board.as_bytes()).unwrap();
+        let mut dumped = Vec::new();
+        game.dump_to_writer(&mut dumped).unwrap();
+        assert_eq!(board.as_bytes(), dumped);
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let mut game = make_board([
+            ".........",
+            ".1234678.",
+            "...7.0.2.",
+            ".1234678.",
+            ".123.679.",
+            ".1...638.",
+            ".12.4670.",
+            ".1234678.",
+            ".........",
+        ]);
+        game.place_next(Cursor { x: 0, y: 0 });
+        game.place_next(Cursor { x: 8, y: 8 });
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+
+        let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+        assert_eq!(board_of(&game), board_of(&loaded));
+        assert_eq!(game.magazine.nexts, loaded.magazine.nexts);
+        assert_eq!(game.num_placed, loaded.num_placed);
+        assert_eq!(game.stats(), loaded.stats());
+        assert_eq!(
+            game.board.iter().filter(|c| c.is_some()).count(),
+            loaded.num_remaining
+        );
+
+        // ~ a different format version must be rejected
+        let saved = String::from_utf8(saved).unwrap();
+        let saved = saved.replace("summing-save 1", "summing-save 2");
+        assert!(Game::load_full_from_reader(saved.as_bytes(), ConstantRng).is_err());
+
+        // ~ as must miscounted stones
+        for stones in ["stones 1/0", "stones 1 0 0 0 0 0 0 0 0 0", "stones x/0 0/0"] {
+            let saved = saved.replace("summing-save 2", "summing-save 1");
+            let saved = saved.replacen("par ", &format!("{stones}\npar "), 1);
+            assert!(Game::load_full_from_reader(saved.as_bytes(), ConstantRng).is_err());
+        }
+
+        // ~ so must a board larger than supported
+        for size in ["256 9", "9 256", "100000000000 100000000000"] {
+            let saved = saved
+                .replace("summing-save 2", "summing-save 1")
+                .replacen("9 9", size, 1);
+            let Err(err) = Game::load_full_from_reader(saved.as_bytes(), ConstantRng) else {
+                panic!("loaded a {size} board");
+            };
+            assert!(err.to_string().starts_with("board too large"), "{err}");
         }
-        game
     }
 
     #[test]
-    fn test_find_free_any_full_board() {
+    fn test_was_occupied() {
         let mut game = make_board([
-            "000000000",
-            "111111111",
-            "222222222",
-            "333333333",
-            "444444444",
-            "555555555",
-            "666666666",
-            "777777777",
-            "888888888",
+            "1........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
         ]);
-        assert_eq!(None, game.find_free_any(Cursor { x: 0, y: 0 }));
+        let (a, b) = (Cursor { x: 0, y: 0 }, Cursor { x: 1, y: 1 });
+        assert!(game.was_occupied(a));
+        assert!(!game.was_occupied(b));
+        assert!(!game.was_occupied(Cursor { x: 9, y: 0 }));
+        // ~ the 0 stays next to the 1 and is cleared by the next 1
+        game.magazine.nexts = vec![Stone::_0, Stone::_1];
+        assert!(matches!(game.place_next(b), PlaceOutcome::Occupied(_)));
+        assert!(game.was_occupied(b));
+        game.undo();
+        assert!(!game.was_occupied(b));
+        game.redo();
+        assert!(game.was_occupied(b));
+        // ~ cleared cells stay marked; the clearing one never occupies
+        let d = Cursor { x: 1, y: 0 };
+        assert!(matches!(game.place_next(d), PlaceOutcome::Cleared(_)));
+        assert_eq!(None, game.cell(a));
+        assert!(game.was_occupied(a) && game.was_occupied(b));
+        assert!(!game.was_occupied(d));
+        // ~ a new game knows only of its initial stones
+        game.reinit_with_rng(Difficulty::Normal, ConstantRng);
+        assert!(!game.was_occupied(a));
+        assert!(game.was_occupied(b));
     }
 
     #[test]
-    fn test_find_free_any_single_free_cell() {
-        for y in 0..ROWS {
-            for x in 0..COLS {
+    fn test_snapshot_roundtrip() {
+        let mut game = make_board([
+            ".........",
+            ".1234678.",
+            "...7.0.2.",
+            ".1234678.",
+            ".123.679.",
+            ".1...638.",
+            ".12.4670.",
+            ".1234678.",
+            ".........",
+        ]);
+        game.set_finite_bag(&[1, 2, 3]).unwrap();
+        game.place_next(Cursor { x: 0, y: 0 });
+        game.place_next(Cursor { x: 4, y: 2 });
+        let snapshot = game.snapshot();
+        assert_eq!(
+            Some(3 + 3 - 2 - NUM_NEXTS),
+            snapshot.bag.as_ref().map(Vec::len)
+        );
+
+        let mut restored = Game::from_snapshot(&snapshot, ConstantRng).unwrap();
+        assert_eq!(snapshot, restored.snapshot());
+        assert_eq!(board_of(&game), board_of(&restored));
+        assert_eq!(game.num_remaining, restored.num_remaining);
+        assert_eq!(game.stats(), restored.stats());
+        // ~ plays on alike
+        for _ in 0..2 {
+            let point = game.find_hint().unwrap_or_default();
+            assert_eq!(game.place_next(point), restored.place_next(point));
+        }
+        assert_eq!(game.snapshot(), restored.snapshot());
+        // ~ the preview keeps its size though the bag ran short
+        let snapshot = game.snapshot();
+        assert!(snapshot.nexts.len() < NUM_NEXTS);
+        let restored = Game::from_snapshot(&snapshot, ConstantRng).unwrap();
+        assert_eq!(NUM_NEXTS, restored.num_nexts());
+
+        // ~ an inconsistent snapshot is rejected
+        let mut broken = snapshot.clone();
+        broken.board.pop();
+        assert!(Game::from_snapshot(&broken, ConstantRng).is_err());
+        let mut broken = snapshot.clone();
+        broken.base = 5;
+        assert!(Game::from_snapshot(&broken, ConstantRng).is_err());
+        let mut broken = snapshot.clone();
+        (broken.nexts, broken.bag) = (Vec::new(), None);
+        assert!(Game::from_snapshot(&broken, ConstantRng).is_err());
+        let mut broken = snapshot.clone();
+        broken.num_nexts = 0;
+        assert!(Game::from_snapshot(&broken, ConstantRng).is_err());
+        let mut broken = snapshot;
+        broken.cols = MAX_SIZE + 1;
+        broken.board = vec![None; broken.rows * broken.cols];
+        assert!(Game::from_snapshot(&broken, ConstantRng).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_serde() {
+        let mut game = make_board([
+            ".........",
+            ".1234678.",
+            "...7.0.2.",
+            ".1234678.",
+            ".123.679.",
+            ".1...638.",
+            ".12.4670.",
+            ".1234678.",
+            ".........",
+        ]);
+        game.set_neighbour_mode(NeighbourMode::Orthogonal);
+        game.place_next(Cursor { x: 0, y: 0 });
+        let mut snapshot = game.snapshot();
+        snapshot.seed = Some(42);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains(r#""neighbour_mode":"orthogonal""#), "{json}");
+        assert!(json.contains(r#""board":[0,null,"#), "{json}");
+        let parsed: super::GameSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot, parsed);
+        let restored = Game::from_snapshot(&parsed, ConstantRng).unwrap();
+        assert_eq!(board_of(&game), board_of(&restored));
+
+        // ~ stones by their values only
+        let json = json.replace(r#""board":[0,"#, r#""board":[10,"#);
+        assert!(serde_json::from_str::<super::GameSnapshot>(&json).is_err());
+        for d in Direction::all() {
+            let json = serde_json::to_string(&d).unwrap();
+            assert_eq!(d, serde_json::from_str(&json).unwrap());
+        }
+        let cursor = Cursor { x: 3, y: 4 };
+        let json = serde_json::to_string(&cursor).unwrap();
+        assert_eq!(r#"{"x":3,"y":4}"#, json);
+        assert_eq!(cursor, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_from_cells() {
+        let mut cells = vec![None; 12];
+        cells[5] = Some(Stone::_3);
+        cells[6] = Some(Stone::_7);
+        let game = Game::from_cells(ConstantRng, 3, 4, &cells).unwrap();
+        assert_eq!((3, 4, 2), (game.rows(), game.cols(), game.num_remaining));
+        assert_eq!(Some(Stone::_7), game.get(1, 2));
+        assert_eq!(NUM_NEXTS, game.nexts().count());
+        assert!(Game::from_cells(ConstantRng, 4, 4, &cells).is_err());
+        assert!(Game::from_cells(ConstantRng, 2, 6, &cells).is_err());
+        let cells = vec![None; 3 * (MAX_SIZE + 1)];
+        assert!(Game::from_cells(ConstantRng, 3, MAX_SIZE + 1, &cells).is_err());
+        assert!(Game::from_cells(ConstantRng, 3, MAX_SIZE, &cells[3..]).is_ok());
+    }
+
+    #[test]
+    fn test_with_size() {
+        let game = Game::with_size(3, 5, ConstantRng);
+        assert_eq!((3, 5), (game.rows(), game.cols()));
+        assert_eq!(3, game.num_remaining);
+        assert_eq!(Some(Stone::_0), game.get(1, 3));
+        assert_eq!(None, game.get(2, 4));
+        assert_eq!(
+            Some(Cursor { x: 0, y: 2 }),
+            game.find_free_next(Cursor { x: 4, y: 1 }, super::Direction::East)
+        );
+        assert_eq!(
+            Some(Cursor { x: 2, y: 0 }),
+            game.find_free_any(Cursor { x: 2, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_neighbour_modes() {
+        let board = [
+            ".........",
+            ".........",
+            ".........",
+            "...123...",
+            "...4.5...",
+            "...679...",
+            ".........",
+            ".........",
+            ".........",
+        ];
+        let center = Cursor { x: 4, y: 4 };
+        for (mode, sum, remaining) in [
+            (NeighbourMode::All, '7', [".....", ".....", "....."]),
+            (NeighbourMode::Orthogonal, '8', [".1.3.", ".....", ".6.9."]),
+            (NeighbourMode::Diagonal, '9', ["..2..", ".4.5.", "..7.."]),
+        ] {
+            let mut game = make_board(board);
+            game.set_neighbour_mode(mode);
+            let cnt = if mode == NeighbourMode::All { 8 } else { 4 };
+            let sum = Stone::try_from(sum).unwrap();
+            assert_eq!((cnt, sum as usize), game.neighbour_sum(center), "{mode:?}");
+            game.magazine.nexts[0] = sum;
+            assert_eq!(cnt, game.would_clear(center).count(), "{mode:?}");
+            let outcome = game.place_next(center);
+            assert!(
+                matches!(&outcome, PlaceOutcome::Cleared(p) if p.cleared.len() == cnt),
+                "{mode:?}: {outcome:?}"
+            );
+            let expected = make_board([
+                ".........",
+                ".........",
+                ".........",
+                &format!("..{}..", remaining[0]),
+                &format!("..{}..", remaining[1]),
+                &format!("..{}..", remaining[2]),
+                ".........",
+                ".........",
+                ".........",
+            ]);
+            assert_eq!(board_of(&expected), board_of(&game), "{mode:?}");
+            assert_eq!(8 - cnt, game.num_remaining, "{mode:?}");
+
+            // ~ the mode survives saving and loading
+            let mut saved = Vec::new();
+            game.save_to_writer(&mut saved).unwrap();
+            let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+            assert_eq!(mode, loaded.neighbour_mode());
+        }
+    }
+
+    #[test]
+    fn test_base() {
+        let mut game = Game::new_with_base(5, 5, Difficulty::Hard, 3, 6, Pcg32::seed_from_u64(3));
+        for _ in 0..20 {
+            let Some(p) = game.free_cells().next() else {
+                break;
+            };
+            game.place_next(p);
+        }
+        assert!(game.board.iter().flatten().all(|&s| (s as usize) < 6));
+        assert!(game.nexts().all(|s| (s as usize) < 6));
+        let (cnt, sum) = game.neighbour_sum(Cursor { x: 2, y: 2 });
+        assert!(cnt > 0 && sum < 6);
+
+        // ~ the base survives saving and loading
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+        let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+        assert_eq!(6, loaded.base());
+        // ~ ... and starting over
+        game.reinit_with_rng(Difficulty::Hard, Pcg32::seed_from_u64(4));
+        assert!(game.board.iter().flatten().all(|&s| (s as usize) < 6));
+        assert!(game.nexts().all(|s| (s as usize) < 6));
+        // ~ ... while rejecting stones beyond it
+        let saved = String::from_utf8(saved).unwrap().replace("5 5 6", "5 5 3");
+        assert!(Game::load_full_from_reader(saved.as_bytes(), ConstantRng).is_err());
+    }
+
+    #[test]
+    fn test_find_nearest_clearing() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".......1.",
+            ".........",
+        ]);
+        game.magazine.nexts[0] = Stone::_1;
+        assert_eq!(
+            Some(Cursor { x: 2, y: 2 }),
+            game.find_nearest_clearing(Cursor { x: 3, y: 3 })
+        );
+        assert_eq!(
+            Some(Cursor { x: 6, y: 6 }),
+            game.find_nearest_clearing(Cursor { x: 5, y: 5 })
+        );
+        // ~ the cell itself if it clears
+        assert_eq!(
+            Some(Cursor { x: 0, y: 0 }),
+            game.find_nearest_clearing(Cursor { x: 0, y: 0 })
+        );
+        game.magazine.nexts[0] = Stone::_2;
+        assert_eq!(None, game.find_nearest_clearing(Cursor { x: 3, y: 3 }));
+    }
+
+    #[test]
+    fn test_par() {
+        let mut game = Game::new(ConstantRng);
+        assert_eq!(73, game.par());
+        // ~ a handicap doesn't count against the par
+        game.set_start_score(10);
+        assert_eq!(83, game.par());
+        // ~ ... and is kept by saving and loading
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+        let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+        assert_eq!(83, loaded.par());
+        // ~ even past the par
+        game.num_placed = 100;
+        game.set_start_score(90);
+        assert_eq!(73, game.par());
+        // ~ lowering the count after placements lowers the par alike
+        game.num_placed = 200;
+        game.set_start_score(150);
+        assert_eq!(23, game.par());
+        game.set_start_score(0);
+        assert_eq!((0, 0), (game.par(), game.num_placed()));
+    }
+
+    #[test]
+    fn test_magazine() {
+        use super::Magazine;
+        use std::collections::VecDeque;
+
+        // ~ rewinding replays the very same stones
+        let mut rng = Pcg32::seed_from_u64(1);
+        let mut m = Magazine::new(3, MAX_BASE, &mut rng);
+        let nexts = m.peek(3).to_vec();
+        assert_eq!(&nexts[..2], m.peek(2));
+        let served: Vec<_> = (0..5).map(|_| m.advance(&mut rng).unwrap()).collect();
+        assert_eq!(nexts, served[..3]);
+        let preview = m.peek(3).to_vec();
+        for &s in served.iter().rev() {
+            m.rewind(s);
+        }
+        assert_eq!(nexts, m.peek(3));
+        let replayed: Vec<_> = (0..5).map(|_| m.advance(&mut rng).unwrap()).collect();
+        assert_eq!(served, replayed);
+        assert_eq!(preview, m.peek(3));
+        assert_eq!(None, m.bag_len());
+
+        // ~ a bag runs out
+        let bag = VecDeque::from([Stone::_1, Stone::_2, Stone::_3]);
+        let mut m = Magazine::with_bag(2, bag);
+        assert_eq!(
+            (&[Stone::_1, Stone::_2][..], Some(1)),
+            (m.peek(2), m.bag_len())
+        );
+        for (s, left) in [(Stone::_1, 2), (Stone::_2, 1), (Stone::_3, 0)] {
+            assert_eq!(Some(s), m.advance(&mut rng));
+            assert_eq!((left, Some(0)), (m.peek(2).len(), m.bag_len()));
+        }
+        assert_eq!(None, m.advance(&mut rng));
+        m.rewind(Stone::_3);
+        m.rewind(Stone::_2);
+        m.rewind(Stone::_1);
+        assert_eq!(
+            (&[Stone::_1, Stone::_2][..], Some(1)),
+            (m.peek(2), m.bag_len())
+        );
+        assert_eq!(vec![Stone::_3], m.bag().unwrap().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_finite_bag() {
+        let mut game = Game::new_with_base(5, 7, Difficulty::Easy, 2, 3, Pcg32::seed_from_u64(1));
+        assert!(game.set_finite_bag(&[1; 4]).is_err());
+        assert_eq!(None, game.bag_len());
+        game.set_finite_bag(&[2, 0, 1]).unwrap();
+        assert_eq!(Some(1), game.bag_len());
+        let mut stones: Vec<_> = game
+            .nexts()
+            .chain(game.magazine.bag().into_iter().flatten())
+            .collect();
+        stones.sort();
+        assert_eq!(vec![Stone::_0, Stone::_0, Stone::_2], stones);
+
+        let nexts: Vec<_> = game.nexts().collect();
+        let point = game.free_cells().next().unwrap();
+        game.place_next(point);
+        assert_eq!((Some(0), 2), (game.bag_len(), game.nexts().count()));
+        // ~ undo hands the stone back to the bag
+        game.undo();
+        assert_eq!(Some(1), game.bag_len());
+        assert_eq!(nexts, game.nexts().collect::<Vec<_>>());
+
+        // ~ the bag survives saving and loading; even with stones
+        // handed back
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+        let mut loaded =
+            Game::load_full_from_reader(saved.as_slice(), Pcg32::seed_from_u64(2)).unwrap();
+        assert_eq!(Some(1), loaded.bag_len());
+
+        // ~ the game is lost once the magazine runs dry
+        for game in [&mut game, &mut loaded] {
+            for n in [2, 1, 0] {
+                let point = game.free_cells().next().unwrap();
+                game.place_next(point);
+                assert_eq!((Some(0), n), (game.bag_len(), game.nexts().count()));
+            }
+            assert!(game.num_remaining > 0);
+            assert!(game.find_hint().is_none());
+            assert!(matches!(game.is_finished(), Some(Finished::Failure)));
+            game.undo();
+            assert!(game.is_finished().is_none());
+            assert_eq!(2, game.num_nexts());
+        }
+        assert_eq!(game.magazine.nexts, loaded.magazine.nexts);
+
+        // ~ an empty magazine is valid only with a bag
+        let mut saved = Vec::new();
+        let point = game.free_cells().next().unwrap();
+        game.place_next(point);
+        game.save_to_writer(&mut saved).unwrap();
+        let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+        // ~ keeping the size of the preview
+        assert_eq!((0, 2), (loaded.nexts().count(), loaded.num_nexts()));
+        let saved = String::from_utf8(saved).unwrap().replace("bag \n", "");
+        assert!(Game::load_full_from_reader(saved.as_bytes(), ConstantRng).is_err());
+
+        // ~ starting over returns to the endless stream
+        game.reinit_with_rng(Difficulty::Easy, Pcg32::seed_from_u64(3));
+        assert_eq!((None, 2), (game.bag_len(), game.nexts().count()));
+    }
+
+    #[test]
+    fn test_reinit_with_rng() {
+        let mut game =
+            Game::new_with_base(5, 7, Difficulty::Easy, 2, MAX_BASE, Pcg32::seed_from_u64(1));
+        game.place_next(Cursor { x: 0, y: 0 });
+        game.undo();
+        game.reinit_with_rng(Difficulty::Hard, Pcg32::seed_from_u64(2));
+        let mut fresh =
+            Game::new_with_base(5, 7, Difficulty::Hard, 2, MAX_BASE, Pcg32::seed_from_u64(2));
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        game.save_to_writer(&mut a).unwrap();
+        fresh.save_to_writer(&mut b).unwrap();
+        assert_eq!(String::from_utf8(b).unwrap(), String::from_utf8(a).unwrap());
+        assert!(game.redo().is_none());
+        // ~ both continue with the same stones
+        game.place_next(Cursor { x: 0, y: 0 });
+        fresh.place_next(Cursor { x: 0, y: 0 });
+        assert_eq!(
+            fresh.nexts().collect::<Vec<_>>(),
+            game.nexts().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_seed_is_stable() {
+        // ~ a seed must yield the very same game forever; the sharing
+        // of seeds relies on it
+        let mut game = Game::new(Pcg32::seed_from_u64(42));
+        game.place_next(Cursor { x: 0, y: 0 });
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+        assert_eq!(
+            "summing-save 1
+9 9
+1
+0675
+.........
+..302154.
+.1633278.
+.3077948.
+.8946212.
+.6412558.
+.4437808.
+.9619374.
+.........
+cleared 1
+stones 0/0 0/0 0/0 0/0 0/0 1/1 0/0 0/0 0/0 0/0
+par 73
+",
+            String::from_utf8(saved).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let rng = Pcg32::seed_from_u64;
+        let normal = Game::new(rng(7));
+        assert_eq!((ROWS - 2) * (COLS - 2), normal.num_remaining);
+        for (difficulty, cmp) in [
+            (Difficulty::Easy, std::cmp::Ordering::Less),
+            (Difficulty::Hard, std::cmp::Ordering::Greater),
+        ] {
+            let game = Game::new_with_base(ROWS, COLS, difficulty, NUM_NEXTS, MAX_BASE, rng(7));
+            assert_eq!(cmp, game.num_remaining.cmp(&normal.num_remaining));
+            assert_eq!(
+                game.board.iter().filter(|c| c.is_some()).count(),
+                game.num_remaining
+            );
+        }
+    }
+
+    #[test]
+    fn test_neighbour_sum() {
+        let game = make_board([
+            "9.......3",
+            "12.......",
+            ".........",
+            ".........",
+            "....5....",
+            "...4.6...",
+            ".........",
+            ".........",
+            "7.......8",
+        ]);
+        assert_eq!((3, 2), game.neighbour_sum(Cursor { x: 1, y: 0 }));
+        assert_eq!((2, 3), game.neighbour_sum(Cursor { x: 0, y: 0 }));
+        assert_eq!((1, 3), game.neighbour_sum(Cursor { x: 7, y: 1 }));
+        assert_eq!((3, 5), game.neighbour_sum(Cursor { x: 4, y: 5 }));
+        assert_eq!((0, 0), game.neighbour_sum(Cursor { x: 4, y: 2 }));
+        assert_eq!((1, 8), game.neighbour_sum(Cursor { x: 7, y: 7 }));
+        assert_eq!((1, 7), game.neighbour_sum(Cursor { x: 1, y: 7 }));
+    }
+
+    #[test]
+    fn test_would_clear() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..9......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        let nexts = game.magazine.nexts.clone();
+        assert_eq!(
+            vec![Cursor { x: 1, y: 1 }, Cursor { x: 2, y: 2 }],
+            game.would_clear(Cursor { x: 2, y: 1 }).collect::<Vec<_>>()
+        );
+        assert_eq!(0, game.would_clear(Cursor { x: 0, y: 0 }).count());
+        assert_eq!(0, game.would_clear(Cursor { x: 5, y: 5 }).count());
+        assert_eq!(nexts, game.magazine.nexts);
+        game.magazine.nexts[0] = Stone::_1;
+        assert_eq!(1, game.would_clear(Cursor { x: 0, y: 0 }).count());
+    }
+
+    #[test]
+    fn test_num_remaining() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..9......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        assert_eq!(2, game.num_remaining());
+        // ~ occupying adds the placed stone
+        game.place_next(Cursor { x: 5, y: 5 });
+        assert_eq!(3, game.num_remaining());
+        // ~ clearing takes away all the neighbours
+        game.magazine.nexts[0] = Stone::_0;
+        game.place_next(Cursor { x: 2, y: 1 });
+        assert_eq!(1, game.num_remaining());
+        game.undo();
+        assert_eq!(3, game.num_remaining());
+        game.redo();
+        assert_eq!(1, game.num_remaining());
+    }
+
+    #[test]
+    fn test_num_remaining_at_edges() {
+        let board = "\
+.1.....2.
+34.6.7.56
+.........
+.........
+.........
+.........
+.........
+78.....16
+.9.....2.
+";
+        let mut game = Game::new(ConstantRng);
+        game.load_from_reader(board.as_bytes()).unwrap();
+        let count = |game: &Game<_>| game.board.iter().flatten().count();
+        assert_eq!(count(&game), game.num_remaining());
+        // ~ clearing in the corners (three neighbours) and along the
+        // edges (five neighbours)
+        for (x, y, next) in [(0, 0, 8), (8, 0, 3), (4, 0, 3), (0, 8, 4), (8, 8, 9)] {
+            game.magazine.nexts[0] = Stone::try_from(next).unwrap();
+            assert!(
+                matches!(game.place_next(Cursor { x, y }), PlaceOutcome::Cleared(_)),
+                "({x}, {y}) didn't clear"
+            );
+            assert_eq!(count(&game), game.num_remaining());
+        }
+        assert_eq!(0, game.num_remaining());
+    }
+
+    #[test]
+    fn test_free_cells() {
+        let game = make_board([
+            "9.......3",
+            "12.......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "........7",
+        ]);
+        let num_stones = game.board.iter().filter(|c| c.is_some()).count();
+        assert_eq!(ROWS * COLS - num_stones, game.free_cells().count());
+        assert!(
+            game.free_cells()
+                .all(|p| game.get(p.y as usize, p.x as usize).is_none())
+        );
+        assert_eq!(
+            vec![Cursor { x: 1, y: 0 }, Cursor { x: 2, y: 0 }],
+            game.free_cells().take(2).collect::<Vec<_>>()
+        );
+
+        let game = Game::with_size(3, 5, ConstantRng);
+        assert_eq!(3 * 5 - 3, game.free_cells().count());
+    }
+
+    #[test]
+    fn test_skip_isolated() {
+        let mut game = make_board([
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "......1..",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        let origin = Cursor { x: 0, y: 0 };
+        assert_eq!(Some(origin), game.find_free_any(origin));
+        assert_eq!(
+            Some(Cursor { x: 1, y: 0 }),
+            game.find_free_next(origin, super::Direction::East)
+        );
+
+        game.set_skip_isolated(true);
+        assert_eq!(Some(Cursor { x: 5, y: 3 }), game.find_free_any(origin));
+        assert_eq!(
+            Some(Cursor { x: 5, y: 3 }),
+            game.find_free_next(origin, super::Direction::East)
+        );
+        assert_eq!(
+            Some(Cursor { x: 5, y: 3 }),
+            game.find_free_next(origin, super::Direction::South)
+        );
+
+        // ~ falls back to any free cell if there are no stones at all
+        game.board.fill(None);
+        game.num_remaining = 0;
+        game.board[0] = Some(Stone::_1);
+        game.num_remaining = 1;
+        assert_eq!(Some(Cursor { x: 1, y: 0 }), game.find_free_any(origin));
+    }
+
+    #[test]
+    fn test_initial_cursor() {
+        let new_game = |seed| {
+            Game::new_with_base(
+                5,
+                6,
+                Difficulty::Easy,
+                2,
+                MAX_BASE,
+                Pcg32::seed_from_u64(seed),
+            )
+        };
+        for seed in 0..20 {
+            let mut game = new_game(seed);
+            let interior = |p: &Cursor| (1..5).contains(&p.x) && (1..4).contains(&p.y);
+            let any_interior = game.free_cells().any(|p| interior(&p));
+            let p = game.initial_cursor();
+            assert_eq!(any_interior, interior(&p), "{p:?}");
+            assert_eq!(None, game.get(p.y as usize, p.x as usize));
+            assert_eq!(p, new_game(seed).initial_cursor());
+        }
+        // ~ falls back to the edges if the interior is taken
+        let mut game = make_board([
+            ".........",
+            ".11111111",
+            ".11111111",
+            ".11111111",
+            ".11111111",
+            ".11111111",
+            ".11111111",
+            ".11111111",
+            "111111111",
+        ]);
+        let p = game.initial_cursor();
+        assert!(p.x == 0 || p.y == 0, "{p:?}");
+        game.board.fill(Some(Stone::_1));
+        assert_eq!(Cursor::default(), game.initial_cursor());
+    }
+
+    #[test]
+    fn test_out_of_bounds_cursor() {
+        let mut game = make_board([
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "......1..",
+            ".........",
+            ".........",
+            ".........",
+            "........1",
+        ]);
+        let board = board_of(&game);
+        for point in [
+            Cursor { x: 9, y: 0 },
+            Cursor { x: 0, y: 9 },
+            Cursor { x: 200, y: 200 },
+        ] {
+            assert_eq!(PlaceOutcome::Refused, game.place_next(point));
+            assert_eq!((board.clone(), 0), (board_of(&game), game.num_placed()));
+            // ~ taken for the closest cell on the board
+            let closest = Cursor {
+                x: point.x.min(8),
+                y: point.y.min(8),
+            };
+            for direction in Direction::all() {
+                assert_eq!(
+                    game.find_free_next(closest, direction),
+                    game.find_free_next(point, direction),
+                    "{point:?} {direction:?}"
+                );
+            }
+            assert_eq!(game.find_free_any(closest), game.find_free_any(point));
+            assert!(game.find_free_any(point).is_some());
+        }
+    }
+
+    #[test]
+    fn test_place_next_occupied() {
+        // ~ the hard difficulty fills some of the border cells; the
+        // corner among them
+        let mut game = (0..)
+            .map(|seed| {
+                Game::new_with_base(
+                    5,
+                    5,
+                    Difficulty::Hard,
+                    3,
+                    MAX_BASE,
+                    Pcg32::seed_from_u64(seed),
+                )
+            })
+            .find(|game| game.get(0, 0).is_some())
+            .unwrap();
+        let board = board_of(&game);
+        assert_eq!(PlaceOutcome::Refused, game.place_next(Cursor::default()));
+        assert_eq!((board, 0), (board_of(&game), game.num_placed()));
+        let free = game.find_free_any(Cursor::default()).unwrap();
+        assert_ne!(Cursor::default(), free);
+        assert_eq!(None, game.get(free.y as usize, free.x as usize));
+    }
+
+    #[test]
+    fn test_find_free_next_reaches_every_cell() {
+        // ~ wrapping around, the only free cell is found from every
+        // cell of the board in every direction
+        for (rows, cols) in [(3, 3), (3, 5), (5, 3), (4, 7), (9, 9)] {
+            for free in 0..rows * cols {
+                let mut cells = vec![Some(Stone::_1); rows * cols];
+                cells[free] = None;
+                let mut game = Game::from_cells(ConstantRng, rows, cols, &cells).unwrap();
+                let free = Cursor {
+                    x: (free % cols) as u8,
+                    y: (free / cols) as u8,
+                };
+                for start in 0..rows * cols {
+                    let start = Cursor {
+                        x: (start % cols) as u8,
+                        y: (start / cols) as u8,
+                    };
+                    for direction in Direction::all() {
+                        game.set_wrap(true);
+                        assert_eq!(
+                            Some(free),
+                            game.find_free_next(start, direction),
+                            "{rows}x{cols} {start:?} {direction:?}"
+                        );
+                        // ~ without wrapping, only if it's ahead
+                        let ahead = match direction {
+                            Direction::North => free.x == start.x && free.y < start.y,
+                            Direction::South => free.x == start.x && free.y > start.y,
+                            Direction::East => free.y == start.y && free.x > start.x,
+                            Direction::West => free.y == start.y && free.x < start.x,
+                        };
+                        game.set_wrap(false);
+                        assert_eq!(
+                            Some(if ahead { free } else { start }),
+                            game.find_free_next(start, direction),
+                            "{rows}x{cols} {start:?} {direction:?} (no wrap)"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_free_next_is_closest() {
+        // ~ with two free cells, the one ahead in the scanning order is
+        // found; or the only other one if starting at a free cell
+        let cursor = |i: usize, cols: usize| Cursor {
+            x: (i % cols) as u8,
+            y: (i / cols) as u8,
+        };
+        for (rows, cols) in [(3, 3), (3, 5), (4, 7)] {
+            for a in 0..rows * cols {
+                for b in a + 1..rows * cols {
+                    let mut cells = vec![Some(Stone::_1); rows * cols];
+                    cells[a] = None;
+                    cells[b] = None;
+                    let game = Game::from_cells(ConstantRng, rows, cols, &cells).unwrap();
+                    let (a, b) = (cursor(a, cols), cursor(b, cols));
+                    for start in 0..rows * cols {
+                        let start = cursor(start, cols);
+                        for direction in Direction::all() {
+                            let expected = [a, b]
+                                .into_iter()
+                                .filter(|&p| p != start)
+                                .map(|p| (p, game.scan_distance(start, p, direction)))
+                                .min_by_key(|&(_, d)| d);
+                            assert_eq!(
+                                expected,
+                                game.find_free_next_with_distance(start, direction),
+                                "{rows}x{cols} {a:?} {b:?} {start:?} {direction:?}"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_goal_clears() {
+        // ~ two stones next to each other which the next stone clears,
+        // and another one far away
+        let next = make_board(["........."; 9]).nexts().next().unwrap() as u8;
+        let mut cells = vec![None; ROWS * COLS];
+        cells[COLS + 1] = Some(Stone::_1);
+        cells[6 * COLS + 5] = Some(Stone::_1);
+        cells[6 * COLS + 6] = Some(Stone::try_from((next + 9) % 10).unwrap());
+        let mut game = Game::from_cells(ConstantRng, ROWS, COLS, &cells).unwrap();
+        game.set_goal_clears(Some(2));
+        assert_eq!(Some(2), game.goal_clears());
+        game.place_next(Cursor { x: 6, y: 7 });
+        assert_eq!((2, 1), (game.num_cleared(), game.num_remaining()));
+        assert_eq!(Some(Finished::Success), game.is_finished());
+        game.undo();
+        assert_eq!(0, game.num_cleared());
+        assert_eq!(None, game.is_finished());
+        game.set_goal_clears(None);
+        game.redo();
+        assert_eq!(2, game.num_cleared());
+        assert_eq!(None, game.is_finished());
+
+        // ~ the goal and the progress towards it are kept by saving and
+        // loading
+        game.set_goal_clears(Some(3));
+        let mut saved = Vec::new();
+        game.save_to_writer(&mut saved).unwrap();
+        let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+        assert_eq!((Some(3), 2), (loaded.goal_clears(), loaded.num_cleared()));
+    }
+
+    #[test]
+    fn test_find_free_nearest() {
+        let game = make_board([
+            "111111111",
+            "111.11111",
+            "111111111",
+            "11111.111",
+            "111111111",
+            "1.1111111",
+            "111111111",
+            "111111111",
+            "11111111.",
+        ]);
+        let nearest = |x, y| game.find_free_nearest(Cursor { x, y });
+        let at = |x, y| Some(Cursor { x, y });
+        // ~ itself if free
+        assert_eq!(at(5, 3), nearest(5, 3));
+        // ~ diagonal steps count as one
+        assert_eq!(at(5, 3), nearest(6, 4));
+        assert_eq!(at(8, 8), nearest(7, 7));
+        // ~ ties go to the first in reading order: (3, 1) and (5, 3)
+        // are both one away from (4, 2); all three are two away from
+        // (3, 3)
+        assert_eq!(at(3, 1), nearest(4, 2));
+        assert_eq!(at(3, 1), nearest(3, 3));
+        // ~ (5, 3) and (1, 5) are both two away from (3, 5)
+        assert_eq!(at(5, 3), nearest(3, 5));
+        // ~ out of bounds taken for the closest cell on the board
+        assert_eq!(at(8, 8), nearest(200, 200));
+        assert_eq!(
+            None,
+            make_board(["111111111"; 9]).find_free_nearest(Cursor::default())
+        );
+    }
+
+    #[test]
+    fn test_direction() {
+        let all = Direction::all();
+        for (i, d) in all.into_iter().enumerate() {
+            assert_eq!(d, d.opposite().opposite());
+            assert_eq!(all[(i + 2) % 4], d.opposite());
+        }
+    }
+
+    #[test]
+    fn test_remaining_sum() {
+        let game = make_board([
+            "1........",
+            ".........",
+            "....9....",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "..3......",
+            "........5",
+        ]);
+        assert_eq!(18, game.remaining_sum());
+        let game = make_board(["........."; 9]);
+        assert_eq!(0, game.remaining_sum());
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut game = Game::new(Pcg32::seed_from_u64(11));
+        let mut last = game.stats();
+        assert_eq!((0, ROWS * COLS), (last.placed, last.total_cells));
+        assert_eq!(NUM_NEXTS, last.nexts.len());
+        while game.is_finished().is_none() {
+            let point = game
+                .find_hint()
+                .or_else(|| game.free_cells().next())
+                .unwrap();
+            let next = last.nexts[0] as usize;
+            let cleared = matches!(game.place_next(point), PlaceOutcome::Cleared(_));
+            let stats = game.stats();
+            assert_eq!(last.placed + 1, stats.placed);
+            let (mut placed, mut clearing) = (last.placed_per_stone, last.clearing_per_stone);
+            placed[next] += 1;
+            clearing[next] += cleared as usize;
+            assert_eq!(
+                (placed, clearing),
+                (stats.placed_per_stone, stats.clearing_per_stone)
+            );
+            assert!(stats.remaining <= stats.total_cells, "{stats:?}");
+            assert_eq!(game.num_remaining(), stats.remaining);
+            assert_eq!(last.nexts[1..], stats.nexts[..NUM_NEXTS - 1]);
+            last = stats;
+        }
+        assert_eq!(
+            game.num_placed(),
+            last.placed_per_stone.iter().sum::<usize>()
+        );
+        // ~ taken back by undo
+        game.undo();
+        game.undo();
+        let stats = game.stats();
+        assert_eq!(
+            game.num_placed(),
+            stats.placed_per_stone.iter().sum::<usize>()
+        );
+        assert!(stats.clearing_per_stone.iter().sum::<usize>() <= game.num_placed());
+        game.reinit_with_rng(Difficulty::Normal, Pcg32::seed_from_u64(12));
+        assert_eq!([0; super::NUM_STONES], game.stats().placed_per_stone);
+    }
+
+    #[test]
+    fn test_rows_iter_cell() {
+        let mut cells = vec![None; 3 * 4];
+        cells[1] = Some(Stone::_1);
+        cells[11] = Some(Stone::_9);
+        let game = Game::from_cells(ConstantRng, 3, 4, &cells).unwrap();
+        let rows: Vec<_> = game.rows_iter().collect();
+        assert_eq!(3, rows.len());
+        assert_eq!([None, Some(Stone::_1), None, None], rows[0]);
+        assert_eq!([None; 4], rows[1]);
+        assert_eq!([None, None, None, Some(Stone::_9)], rows[2]);
+        for (y, row) in game.rows_iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                let p = Cursor {
+                    x: x as u8,
+                    y: y as u8,
+                };
+                assert_eq!(game.get(y, x), c);
+                assert_eq!(game.cell(p), c);
+            }
+        }
+        assert_eq!(None, game.cell(Cursor { x: 4, y: 0 }));
+        assert_eq!(None, game.cell(Cursor { x: 0, y: 3 }));
+    }
+
+    #[test]
+    fn test_try_get() {
+        let mut cells = vec![None; 3 * 4];
+        cells[0] = Some(Stone::_1);
+        cells[11] = Some(Stone::_9);
+        let game = Game::from_cells(ConstantRng, 3, 4, &cells).unwrap();
+        assert_eq!(Some(Some(Stone::_1)), game.try_get(0, 0));
+        assert_eq!(Some(None), game.try_get(0, 3));
+        assert_eq!(Some(None), game.try_get(2, 0));
+        assert_eq!(Some(Some(Stone::_9)), game.try_get(2, 3));
+        // ~ just past the edges; even where `row * cols + col` would
+        // still be within the board
+        assert_eq!(None, game.try_get(0, 4));
+        assert_eq!(None, game.try_get(1, 4));
+        assert_eq!(None, game.try_get(3, 0));
+        assert_eq!(None, game.try_get(usize::MAX, usize::MAX));
+    }
+
+    #[test]
+    fn test_compact_string() {
+        let mut game = Game::new(Pcg32::seed_from_u64(5));
+        let point = game.free_cells().next().unwrap();
+        game.place_next(point);
+        let line = game.to_compact_string(None);
+        assert_eq!(ROWS * COLS, line.len());
+        let mut dumped = Vec::new();
+        game.dump_to_writer(&mut dumped).unwrap();
+        assert_eq!(String::from_utf8(dumped).unwrap().replace('\n', ""), line);
+
+        // ~ round trip with and without the seed
+        let (restored, seed) = Game::from_compact_string(&line, ROWS, COLS, ConstantRng).unwrap();
+        assert_eq!((board_of(&game), None), (board_of(&restored), seed));
+        assert_eq!(0, restored.num_placed());
+        let line = game.to_compact_string(Some(5));
+        assert!(line.starts_with("5:1:"), "{line}");
+        let (restored, seed) = Game::from_compact_string(&line, ROWS, COLS, ConstantRng).unwrap();
+        assert_eq!((board_of(&game), Some(5)), (board_of(&restored), seed));
+        assert_eq!(1, restored.num_placed());
+
+        // ~ the wrong length, bad cells, or a broken prefix
+        let cells = &line[4..];
+        for bad in [
+            cells[1..].to_owned(),
+            format!("{cells}."),
+            cells.replacen('.', "x", 1),
+            format!("x:1:{cells}"),
+            format!("1:{cells}"),
+        ] {
+            assert!(
+                Game::from_compact_string(&bad, ROWS, COLS, ConstantRng).is_err(),
+                "{bad}"
+            );
+        }
+        let huge = ".".repeat(3 * (MAX_SIZE + 1));
+        assert!(Game::from_compact_string(&huge, MAX_SIZE + 1, 3, ConstantRng).is_err());
+    }
+
+    #[test]
+    fn test_observer() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..9......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        game.set_observer(Some(Box::new({
+            let events = Rc::clone(&events);
+            move |e: &GameEvent| {
+                events.borrow_mut().push(match e {
+                    GameEvent::Placed(p, _) => format!("placed {} {}", p.x, p.y),
+                    GameEvent::Cleared(p, placement) => {
+                        format!("cleared {} {} {}", p.x, p.y, placement.cleared.len())
+                    }
+                    GameEvent::Finished(f) => format!("finished {f:?}"),
+                })
+            }
+        })));
+        game.place_next(Cursor { x: 0, y: 0 });
+        game.undo();
+        game.redo();
+        game.undo();
+        game.place_next(Cursor { x: 2, y: 1 });
+        assert_eq!(
+            ["placed 0 0", "cleared 2 1 2", "finished Success"],
+            events.borrow().as_slice()
+        );
+
+        game.set_observer(None);
+        game.undo();
+        game.place_next(Cursor { x: 2, y: 1 });
+        assert_eq!(3, events.borrow().len());
+    }
+
+    #[test]
+    fn test_zen() {
+        let mut game = Game::from_cells(ConstantRng, 3, 3, &[Some(Stone::_1); 9]).unwrap();
+        let center = Cursor { x: 1, y: 1 };
+        game.board[4] = None;
+        game.num_remaining = 8;
+        assert_eq!(None, game.is_finished());
+        let board = board_of(&game);
+        game.set_zen(true);
+        // ~ filling up the board clears the stone and its neighbours
+        // instead
+        let outcome = game.place_next(center);
+        let PlaceOutcome::Cleared(placement) = outcome else {
+            panic!("{outcome:?}");
+        };
+        assert_eq!((8, 8), (placement.num_neighbours, placement.cleared.len()));
+        assert_eq!(vec![None; 9], board_of(&game));
+        assert_eq!((0, 1), (game.num_remaining(), game.num_placed()));
+        assert!(matches!(game.is_finished(), Some(Finished::Success)));
+        assert_eq!(Some(center), game.undo());
+        assert_eq!((board.clone(), 8), (board_of(&game), game.num_remaining()));
+        assert_eq!(Some(center), game.redo());
+        assert_eq!(vec![None; 9], board_of(&game));
+        game.undo();
+
+        // ~ without zen, the full board is the end of the game
+        game.set_zen(false);
+        assert!(matches!(game.place_next(center), PlaceOutcome::Occupied(_)));
+        assert!(matches!(game.is_finished(), Some(Finished::Failure)));
+        // ~ nor does zen help once it is full
+        game.set_zen(true);
+        assert!(matches!(game.is_finished(), Some(Finished::Failure)));
+
+        // ~ kept by saving and loading
+        for zen in [false, true] {
+            game.set_zen(zen);
+            let mut saved = Vec::new();
+            game.save_to_writer(&mut saved).unwrap();
+            let loaded = Game::load_full_from_reader(saved.as_slice(), ConstantRng).unwrap();
+            assert_eq!(zen, loaded.is_zen());
+        }
+    }
+
+    #[test]
+    fn test_refuse_isolated_placement() {
+        let mut game = make_board([
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            "......1..",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        game.set_allow_isolated_placement(false);
+        let next = game.nexts().next();
+        assert_eq!(
+            PlaceOutcome::Refused,
+            game.place_next(Cursor { x: 0, y: 0 })
+        );
+        // ~ the game is left untouched
+        assert_eq!(None, game.get(0, 0));
+        assert_eq!((0, 1), (game.num_placed(), game.num_remaining()));
+        assert_eq!(next, game.nexts().next());
+        assert_eq!(None, game.undo());
+
+        assert_ne!(
+            PlaceOutcome::Refused,
+            game.place_next(Cursor { x: 5, y: 3 })
+        );
+        assert_eq!(1, game.num_placed());
+
+        game.set_allow_isolated_placement(true);
+        assert!(matches!(
+            game.place_next(Cursor { x: 0, y: 0 }),
+            PlaceOutcome::Occupied(_)
+        ));
+    }
+
+    #[test]
+    fn test_find_free_next_with_distance() {
+        use super::Direction::{East, North, South, West};
+        let game = make_board([
+            "..1.....1",
+            ".........",
+            ".........",
+            ".........",
+            "......1..",
+            ".........",
+            ".........",
+            ".........",
+            "1.......1",
+        ]);
+        let with_distance =
+            |x, y, direction| game.find_free_next_with_distance(Cursor { x, y }, direction);
+        let at = |x, y, d| Some((Cursor { x, y }, d));
+        assert_eq!(at(1, 0, 1), with_distance(0, 0, East));
+        assert_eq!(at(3, 0, 2), with_distance(1, 0, East));
+        // ~ wrapping around to the next row and the board's other end
+        assert_eq!(at(0, 1, 2), with_distance(7, 0, East));
+        assert_eq!(at(0, 0, 2), with_distance(7, 8, East));
+        assert_eq!(at(7, 8, 2), with_distance(0, 0, West));
+        assert_eq!(at(8, 7, 2), with_distance(0, 0, North));
+        assert_eq!(at(6, 5, 2), with_distance(6, 3, South));
+        assert_eq!(at(1, 0, 2), with_distance(0, 7, South));
+    }
+
+    #[test]
+    fn test_no_wrap() {
+        use super::Direction::{East, North, South, West};
+        let mut game = make_board([
+            "..1......",
+            ".........",
+            ".........",
+            ".........",
+            "......1..",
+            ".........",
+            ".........",
+            ".........",
+            "........1",
+        ]);
+        game.set_wrap(false);
+        let at = |x, y| Some(Cursor { x, y });
+        let origin = Cursor { x: 0, y: 0 };
+        // ~ stops at the edges
+        assert_eq!(at(0, 0), game.find_free_next(origin, North));
+        assert_eq!(at(0, 0), game.find_free_next(origin, West));
+        assert_eq!(at(8, 7), game.find_free_next(Cursor { x: 8, y: 7 }, South));
+        assert_eq!(at(8, 3), game.find_free_next(Cursor { x: 8, y: 3 }, East));
+        // ~ but jumps over stones
+        assert_eq!(at(1, 0), game.find_free_next(origin, East));
+        assert_eq!(at(3, 0), game.find_free_next(Cursor { x: 1, y: 0 }, East));
+        assert_eq!(at(1, 0), game.find_free_next(Cursor { x: 3, y: 0 }, West));
+        assert_eq!(at(6, 5), game.find_free_next(Cursor { x: 6, y: 3 }, South));
+        assert_eq!(at(6, 3), game.find_free_next(Cursor { x: 6, y: 5 }, North));
+
+        // ~ along with the distance moved
+        assert_eq!(
+            Some((Cursor { x: 3, y: 0 }, 2)),
+            game.find_free_next_with_distance(Cursor { x: 1, y: 0 }, East)
+        );
+        assert_eq!(
+            Some((origin, 0)),
+            game.find_free_next_with_distance(origin, North)
+        );
+
+        // ~ preferring free cells next to a stone stays within the row
+        game.set_skip_isolated(true);
+        assert_eq!(at(5, 3), game.find_free_next(Cursor { x: 0, y: 3 }, East));
+        assert_eq!(at(0, 1), game.find_free_next(origin, South));
+    }
+
+    #[test]
+    fn test_deadlock() {
+        // ~ isolated free cells still allow clearing their neighbours
+        let game = make_board([
+            "1111.1111",
+            "111111111",
+            ".1111111.",
+            "111111111",
+            "1111.1111",
+            "111111111",
+            "111111111",
+            "11111111.",
+            "111111111",
+        ]);
+        assert!(!game.is_deadlocked());
+        assert!(game.is_finished().is_none());
+
+        let mut game = make_board(["111111111"; ROWS]);
+        assert!(game.is_deadlocked());
+        assert!(matches!(game.is_finished(), Some(super::Finished::Failure)));
+
+        // ~ a single stone leaves its neighbours to clear it
+        game.board.fill(None);
+        game.board[0] = Some(Stone::_5);
+        game.num_remaining = 1;
+        assert!(!game.is_deadlocked());
+
+        // ~ the halves of the board are apart with diagonal neighbours
+        let mut game = make_board([
+            "1.1.1.1.1",
+            ".1.1.1.1.",
+            "1.1.1.1.1",
+            ".1.1.1.1.",
+            "1.1.1.1.1",
+            ".1.1.1.1.",
+            "1.1.1.1.1",
+            ".1.1.1.1.",
+            "1.1.1.1.1",
+        ]);
+        for mode in NeighbourMode::ALL {
+            game.set_neighbour_mode(mode);
+            assert_eq!(
+                mode == NeighbourMode::Diagonal,
+                game.is_deadlocked(),
+                "{mode:?}"
+            );
+        }
+        game.board[1] = Some(Stone::_1);
+        game.num_remaining += 1;
+        assert!(!game.is_deadlocked());
+    }
+
+    #[test]
+    fn test_find_hint() {
+        let mut game = make_board([
+            ".........",
+            ".1.......",
+            "..8......",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+            ".........",
+        ]);
+        assert_eq!(None, game.find_hint());
+        // ~ (1 + 8 + 1) % 10 == 0
+        game.board[3] = Some(Stone::_1);
+        assert_eq!(Some(Cursor { x: 2, y: 1 }), game.find_hint());
+    }
+
+    #[test]
+    fn test_find_free_any_full_board() {
+        let game = make_board([
+            "000000000",
+            "111111111",
+            "222222222",
+            "333333333",
+            "444444444",
+            "555555555",
+            "666666666",
+            "777777777",
+            "888888888",
+        ]);
+        assert_eq!(None, game.find_free_any(Cursor { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_find_free_any_single_free_cell() {
+        for y in 0..ROWS {
+            for x in 0..COLS {
                 let mut game = make_board([
                     "000000000",
                     "111111111",
@@ -537,6 +3682,7 @@ mod tests {
                     "888888888",
                 ]);
                 game.board[y * COLS + x] = None;
+                game.num_remaining -= 1;
                 // ~ start at the free cell itself
                 assert_eq!(
                     Some(Cursor {
@@ -565,7 +3711,7 @@ mod tests {
 
     #[test]
     fn test_find_free_any_closest_0() {
-        let mut game = make_board([
+        let game = make_board([
             "000000.00",
             "111111111",
             "222222222",
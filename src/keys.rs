@@ -0,0 +1,594 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{config, paths};
+
+/// The actions which can be bound to keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    MoveNorth,
+    MoveSouth,
+    MoveEast,
+    MoveWest,
+    Place,
+    Undo,
+    Redo,
+    Hint,
+    Target,
+    Center,
+    Sums,
+    Trail,
+    Need,
+    Mark,
+    Wide,
+    Save,
+    Quit,
+    Help,
+    Keys,
+    Stats,
+    NewGame,
+    Restart,
+    EnterSeed,
+}
+
+impl Action {
+    /// The name of the action as used in the keys configuration file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveNorth => "move_north",
+            Action::MoveSouth => "move_south",
+            Action::MoveEast => "move_east",
+            Action::MoveWest => "move_west",
+            Action::Place => "place",
+            Action::Undo => "undo",
+            Action::Redo => "redo",
+            Action::Hint => "hint",
+            Action::Target => "target",
+            Action::Center => "center",
+            Action::Sums => "sums",
+            Action::Trail => "trail",
+            Action::Need => "need",
+            Action::Mark => "mark",
+            Action::Wide => "wide",
+            Action::Save => "save",
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::Keys => "keys",
+            Action::Stats => "stats",
+            Action::NewGame => "new_game",
+            Action::Restart => "restart",
+            Action::EnterSeed => "enter_seed",
+        }
+    }
+
+    /// What the action does in a few words; for the overview of the
+    /// key bindings (see `Keymap::describe`)
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::MoveNorth => "move the cursor up",
+            Action::MoveSouth => "move the cursor down",
+            Action::MoveEast => "move the cursor right",
+            Action::MoveWest => "move the cursor left",
+            Action::Place => "place the next number at the cursor",
+            Action::Undo => "take back the last placement",
+            Action::Redo => "redo an undone placement",
+            Action::Hint => "move the cursor to a clearing place",
+            Action::Target => "move the cursor to the nearest clearing place",
+            Action::Center => "move the cursor to the middle of the board",
+            Action::Sums => "toggle the sums next to the numbers",
+            Action::Trail => "toggle marking the places cleared before",
+            Action::Need => "toggle the digit the next number needs",
+            Action::Mark => "drop or take away the marker",
+            Action::Wide => "switch between the wide and packed layout",
+            Action::Save => "save the game in progress",
+            Action::Quit => "quit; or close this screen",
+            Action::Help => "show the help",
+            Action::Keys => "show the keys",
+            Action::Stats => "show the statistics",
+            Action::NewGame => "start a new game once over",
+            Action::Restart => "abandon the game for a new one",
+            Action::EnterSeed => "type in the seed of the next game",
+        }
+    }
+}
+
+// ~ the keys bound to the actions unless configured otherwise
+const DEFAULT_BINDINGS: [(Action, &[Chord]); 23] = [
+    (
+        Action::MoveNorth,
+        &[Chord::key(KeyCode::Up), Chord::ctrl('p'), Chord::char('k')],
+    ),
+    (
+        Action::MoveSouth,
+        &[
+            Chord::key(KeyCode::Down),
+            Chord::ctrl('n'),
+            Chord::char('j'),
+        ],
+    ),
+    (
+        Action::MoveEast,
+        &[
+            Chord::key(KeyCode::Right),
+            Chord::ctrl('f'),
+            Chord::char('l'),
+        ],
+    ),
+    (
+        Action::MoveWest,
+        &[
+            Chord::key(KeyCode::Left),
+            Chord::ctrl('b'),
+            Chord::char('h'),
+        ],
+    ),
+    (Action::Place, &[Chord::char(' ')]),
+    (Action::Undo, &[Chord::char('u')]),
+    (Action::Redo, &[Chord::char('r'), Chord::ctrl('r')]),
+    (Action::Hint, &[Chord::char('.')]),
+    (Action::Target, &[Chord::char('f')]),
+    (Action::Center, &[Chord::key(KeyCode::Home)]),
+    (Action::Sums, &[Chord::char('t')]),
+    (Action::Trail, &[Chord::char('v')]),
+    (Action::Need, &[Chord::char('i')]),
+    (Action::Mark, &[Chord::char('m')]),
+    (Action::Wide, &[Chord::char('w')]),
+    (Action::Save, &[Chord::char('s')]),
+    (Action::Quit, &[Chord::char('q')]),
+    (Action::Help, &[Chord::char('?')]),
+    (Action::Keys, &[Chord::char(':')]),
+    (Action::Stats, &[Chord::char('S')]),
+    (Action::NewGame, &[Chord::char('n')]),
+    (Action::Restart, &[Chord::char('R')]),
+    (Action::EnterSeed, &[Chord::char('g')]),
+];
+
+// ~ the keys referred to by name (rather than by their character)
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("esc", KeyCode::Esc),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+/// A key along with the modifiers to be held down
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chord {
+    code: KeyCode,
+    // ~ only ever `CONTROL` and/or `ALT`; shift is expressed through
+    // the character of the key itself
+    modifiers: KeyModifiers,
+}
+
+impl Chord {
+    const fn key(code: KeyCode) -> Self {
+        Chord {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::key(KeyCode::Char(c))
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Chord {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Parses a chord like `q`, `ctrl-p`, `alt-left`, or `space`.
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s;
+        loop {
+            if let Some(rest) = key.strip_prefix("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                key = rest;
+            } else if let Some(rest) = key.strip_prefix("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                key = rest;
+            } else {
+                break;
+            }
+        }
+        let code = match NAMED_KEYS.iter().find(|(name, _)| *name == key) {
+            Some(&(_, code)) => code,
+            None => {
+                let mut cs = key.chars();
+                match (cs.next(), cs.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("invalid key: {s:?}"),
+                }
+            }
+        };
+        Ok(Chord { code, modifiers })
+    }
+
+    /// Tells the chord in short for hints on the screen: an arrow for
+    /// the arrow keys, a plain character as is, and any other one in
+    /// angle brackets, e.g. `<space>` or `<ctrl-z>`.
+    pub fn label(&self) -> String {
+        match (self.code, self.modifiers.is_empty()) {
+            (KeyCode::Up, true) => "↑".to_owned(),
+            (KeyCode::Down, true) => "↓".to_owned(),
+            (KeyCode::Left, true) => "←".to_owned(),
+            (KeyCode::Right, true) => "→".to_owned(),
+            (KeyCode::Char(c), true) if c != ' ' => c.to_string(),
+            _ => format!("<{self}>"),
+        }
+    }
+}
+
+impl From<KeyEvent> for Chord {
+    fn from(event: KeyEvent) -> Self {
+        Chord {
+            code: event.code,
+            modifiers: event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt-")?;
+        }
+        match NAMED_KEYS.iter().find(|(_, code)| *code == self.code) {
+            Some((name, _)) => f.write_str(name),
+            None => match self.code {
+                KeyCode::Char(c) => write!(f, "{c}"),
+                code => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+/// Maps keys to actions
+pub struct Keymap {
+    bindings: Vec<(Chord, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            bindings: DEFAULT_BINDINGS
+                .iter()
+                .flat_map(|&(a, cs)| cs.iter().map(move |&c| (c, a)))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Loads the keymap from the user's configuration file, falling
+    /// back to the defaults if there is no such file.
+    pub fn load() -> Result<Self> {
+        let Some(path) = paths::keys_file() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::from_config(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+
+    /// Parses a keymap configuration, e.g.
+    ///
+    /// ```toml
+    /// quit = "ctrl-c"
+    /// undo = ["u", "ctrl-z"]
+    /// ```
+    ///
+    /// Actions not mentioned retain their default keys.
+    pub fn from_config(text: &str) -> Result<Self> {
+        let mut configured = Vec::new();
+        let mut bindings = Vec::new();
+        for entry in config::parse(text)? {
+            let Some(action) = DEFAULT_BINDINGS
+                .iter()
+                .map(|&(a, _)| a)
+                .find(|a| a.name() == entry.key)
+            else {
+                bail!("line {}: unknown action '{}'", entry.line, entry.key);
+            };
+            configured.push(action);
+            for v in &entry.values {
+                let chord = Chord::parse(v).with_context(|| format!("line {}", entry.line))?;
+                bindings.push((chord, action));
+            }
+        }
+        for (chord, action) in Self::default().bindings {
+            if !configured.contains(&action) {
+                bindings.push((chord, action));
+            }
+        }
+        for (i, &(chord, action)) in bindings.iter().enumerate() {
+            if let Some(&(_, other)) = bindings[..i]
+                .iter()
+                .find(|&&(c, a)| c == chord && a != action)
+            {
+                bail!(
+                    "'{chord}' is bound to both '{}' and '{}'",
+                    other.name(),
+                    action.name()
+                );
+            }
+        }
+        Ok(Keymap { bindings })
+    }
+
+    /// Lists the keys bound to each action - as currently configured -
+    /// along with what the action does; one action per line in the
+    /// order of the default bindings.
+    pub fn describe(&self) -> String {
+        let keys: Vec<(Action, String)> = DEFAULT_BINDINGS
+            .iter()
+            .map(|&(action, _)| {
+                let chords: Vec<String> =
+                    self.keys_for(action).iter().map(Chord::to_string).collect();
+                match &chords[..] {
+                    [] => (action, "-".to_owned()),
+                    _ => (action, chords.join(", ")),
+                }
+            })
+            .collect();
+        let width = keys.iter().map(|(_, k)| k.chars().count()).max();
+        let mut text = String::new();
+        for (action, keys) in keys {
+            text.push_str(&format!(
+                "  {keys:<width$}  {}\n",
+                action.description(),
+                width = width.unwrap_or_default()
+            ));
+        }
+        text
+    }
+
+    /// Lists the keys bound to the given action - as currently
+    /// configured - in the order of their configuration; empty if the
+    /// action is unbound.
+    pub fn keys_for(&self, action: Action) -> Vec<Chord> {
+        let mut chords: Vec<Chord> = Vec::new();
+        for &(c, a) in &self.bindings {
+            if a == action && !chords.contains(&c) {
+                chords.push(c);
+            }
+        }
+        chords
+    }
+
+    /// Determines the action bound to the given key event, if any.
+    pub fn action(&self, event: KeyEvent) -> Option<Action> {
+        let chord = Chord::from(event);
+        self.bindings
+            .iter()
+            .find_map(|&(c, a)| if c == chord { Some(a) } else { None })
+    }
+}
+
+// ~ key events of the same action closer than this to each other are
+// taken for the key being held down; generous enough to cover the
+// delay before the terminal starts repeating a held key
+const HOLD_GAP: Duration = Duration::from_millis(600);
+// ~ the pace at which a held movement key moves the cursor; and the
+// quicker one after the key has been held for `ACCELERATE_AFTER`
+const REPEAT_INTERVAL: Duration = Duration::from_millis(150);
+const FAST_REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+const ACCELERATE_AFTER: Duration = Duration::from_secs(1);
+
+/// Smooths the movement of the cursor by a held key: the burst of
+/// repeated events is thinned to a steady pace which speeds up after
+/// the key has been held for a while.
+#[derive(Default)]
+pub struct KeyRepeat {
+    held: Option<Held>,
+}
+
+// ~ a movement key held down
+struct Held {
+    action: Action,
+    // ~ when the key was pressed
+    since: Instant,
+    // ~ when the last event of the key arrived
+    last_event: Instant,
+    // ~ when the last event of the key was let through
+    last_accepted: Instant,
+}
+
+impl KeyRepeat {
+    /// Tells whether the event of `action` arriving at `now` is to be
+    /// handled; otherwise it's a repeat coming too quickly after the
+    /// previous one.  Only movement actions are ever held back.
+    pub fn accept(&mut self, action: Action, now: Instant) -> bool {
+        if !matches!(
+            action,
+            Action::MoveNorth | Action::MoveSouth | Action::MoveEast | Action::MoveWest
+        ) {
+            self.held = None;
+            return true;
+        }
+        match &mut self.held {
+            Some(held)
+                if held.action == action
+                    && now.saturating_duration_since(held.last_event) <= HOLD_GAP =>
+            {
+                held.last_event = now;
+                let interval = if now.saturating_duration_since(held.since) >= ACCELERATE_AFTER {
+                    FAST_REPEAT_INTERVAL
+                } else {
+                    REPEAT_INTERVAL
+                };
+                if now.saturating_duration_since(held.last_accepted) < interval {
+                    return false;
+                }
+                held.last_accepted = now;
+                true
+            }
+            _ => {
+                self.held = Some(Held {
+                    action,
+                    since: now,
+                    last_event: now,
+                    last_accepted: now,
+                });
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Action, Chord, KeyRepeat, Keymap};
+
+    #[test]
+    fn test_chord_parse_display() {
+        for s in [
+            "q",
+            "?",
+            "-",
+            "space",
+            "up",
+            "ctrl-p",
+            "alt-left",
+            "ctrl-alt--",
+            "pageup",
+        ] {
+            assert_eq!(s, Chord::parse(s).unwrap().to_string());
+        }
+        assert!(Chord::parse("ctrl-").is_err());
+        assert!(Chord::parse("foo").is_err());
+    }
+
+    #[test]
+    fn test_keymap() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+
+        let keymap = Keymap::default();
+        assert_eq!(
+            Some(Action::MoveNorth),
+            keymap.action(key(KeyCode::Char('p'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            None,
+            keymap.action(key(KeyCode::Char('p'), KeyModifiers::NONE))
+        );
+
+        let keymap = Keymap::from_config("undo = ['z', 'U']\nquit = []").unwrap();
+        assert_eq!(
+            Some(Action::Undo),
+            keymap.action(key(KeyCode::Char('U'), KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            None,
+            keymap.action(key(KeyCode::Char('u'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            None,
+            keymap.action(key(KeyCode::Char('q'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            Some(Action::Place),
+            keymap.action(key(KeyCode::Char(' '), KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn test_keymap_describe() {
+        let text = Keymap::default().describe();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!("  up, ctrl-p, k     move the cursor up", lines[0]);
+        assert!(lines.contains(&"  space             place the next number at the cursor"));
+        // ~ as configured
+        let text = Keymap::from_config("undo = ['z', 'U', 'z']\nquit = []")
+            .unwrap()
+            .describe();
+        assert!(text.contains("  z, U              take back the last placement\n"));
+        assert!(text.contains("  -                 quit; or close this screen\n"));
+        assert_eq!(
+            Keymap::default().describe().lines().count(),
+            text.lines().count()
+        );
+    }
+
+    #[test]
+    fn test_keys_for() {
+        let labels = |keymap: &Keymap, action| -> Vec<String> {
+            keymap.keys_for(action).iter().map(Chord::label).collect()
+        };
+        let keymap = Keymap::default();
+        assert_eq!(
+            ["←", "<ctrl-b>", "h"],
+            labels(&keymap, Action::MoveWest)[..]
+        );
+        assert_eq!(["<space>"], labels(&keymap, Action::Place)[..]);
+        assert_eq!(["?"], labels(&keymap, Action::Help)[..]);
+        let keymap = Keymap::from_config("undo = ['z', 'ctrl-z', 'z']\nquit = []").unwrap();
+        assert_eq!(["z", "<ctrl-z>"], labels(&keymap, Action::Undo)[..]);
+        assert!(keymap.keys_for(Action::Quit).is_empty());
+    }
+
+    #[test]
+    fn test_keymap_conflicts() {
+        assert!(Keymap::from_config("undo = 'q'").is_err());
+        assert!(Keymap::from_config("undo = 'x'\nredo = 'x'").is_err());
+        assert!(Keymap::from_config("frobnicate = 'x'").is_err());
+        assert!(Keymap::from_config("undo = ['x', 'x']").is_ok());
+    }
+
+    #[test]
+    fn test_key_repeat() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut repeat = KeyRepeat::default();
+        // ~ held down: the terminal repeats the key every 30ms after
+        // an initial delay of 400ms
+        let accepted: Vec<_> = [0]
+            .into_iter()
+            .chain((400..1500).step_by(30))
+            .filter(|&ms| repeat.accept(Action::MoveEast, at(ms)))
+            .collect();
+        assert_eq!(
+            [
+                0, 400, 550, 700, 850, 1000, 1060, 1120, 1180, 1240, 1300, 1360, 1420, 1480
+            ],
+            accepted[..]
+        );
+        // ~ another direction is a fresh press
+        assert!(repeat.accept(Action::MoveWest, at(1500)));
+        assert!(!repeat.accept(Action::MoveWest, at(1530)));
+        // ~ as is one after a pause
+        assert!(repeat.accept(Action::MoveWest, at(2200)));
+        // ~ other actions are never held back, and release the key
+        assert!(repeat.accept(Action::Place, at(2210)));
+        assert!(repeat.accept(Action::Place, at(2220)));
+        assert!(repeat.accept(Action::MoveWest, at(2230)));
+    }
+}
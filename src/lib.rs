@@ -0,0 +1,14 @@
+//! The rules of the game independent of any user interface: the state
+//! of a game along with the placement of stones (`game`), the random
+//! number generator setting up games reproducibly (`rng`), and a simple
+//! strategy playing games on its own (`solver`).
+//!
+//! With the "serde" feature, the plain data types (e.g. `Stone`,
+//! `Cursor`, and `game::GameSnapshot`) implement serde's `Serialize`
+//! and `Deserialize`.
+
+pub mod game;
+pub mod rng;
+pub mod solver;
+
+pub use game::{Cursor, Direction, Finished, Game, NUM_STONES, Stone};
@@ -1,66 +1,326 @@
-use std::{borrow::Cow, io};
+use std::{
+    borrow::Cow,
+    io, mem,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
 
-use anyhow::Result;
-use game::{Cursor, Game};
+use anyhow::{Context, Result};
+use game::{Cursor, Game, PlaceOutcome};
+use keys::{Action, KeyRepeat, Keymap};
 use rand::{Rng, SeedableRng};
 use ratatui::{
     DefaultTerminal, Frame,
     buffer::Buffer,
-    crossterm::event::{self, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    layout::{Alignment, Constraint, Flex, Layout, Position, Rect},
-    style::{Color, Style, Stylize},
+    crossterm::{
+        event::{
+            self, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+            MouseEventKind,
+        },
+        execute, style,
+    },
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Clear, Paragraph, StatefulWidget, Widget},
+    widgets::{
+        Block, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+        Widget, Wrap,
+    },
 };
+use replay::{Replay, Step};
+use summing::{game, rng, solver};
 
 mod args;
-mod game;
+mod config;
+mod date;
+mod keys;
+mod paths;
+mod replay;
+mod stats;
+mod text;
+mod theme;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ~ the exit status telling how a game played without the terminal UI
+// ended (e.g. by '--solve'); 1 is taken by errors (see `main`)
+fn exit_status(finished: Option<game::Finished>) -> u8 {
+    match finished {
+        Some(game::Finished::Success) => 0,
+        // ~ lost, given up by the solver, or quit by the player
+        Some(game::Finished::Failure) | None => 2,
+    }
+}
 
-fn main() -> Result<()> {
-    let args = args::from_env();
-    let rng = rand::rngs::SmallRng::seed_from_u64;
+// ~ plays as told on the command line; returns the exit code of the
+// process unless failing
+fn run() -> Result<ExitCode> {
+    let mut args = args::from_env();
+    let daily = args.daily.then(date::Date::today);
+    if let Some(date) = daily {
+        if args.resume || args.play.is_some() || args.board.is_some() {
+            anyhow::bail!("'--daily' cannot be combined with '--resume', '--play', or '--board'");
+        }
+        args.seed = date.seed();
+    }
+    // ~ pinned to `Pcg32` such that a revealed seed replays the very
+    // same game on any platform
+    let rng = rng::Pcg32::seed_from_u64;
+    if args.solve {
+        // ~ the solver plays just a fresh game as drawn from the seed
+        if args.resume
+            || args.play.is_some()
+            || args.board.is_some()
+            || args.record.is_some()
+            || args.zen
+            || args.goal_clears > 0
+            || args.demo
+            || args.text
+        {
+            anyhow::bail!(
+                "'--solve' cannot be combined with '--resume', '--play', '--board', \
+                 '--record', '--zen', '--goal-clears', '--demo', or '--text'"
+            );
+        }
+        let mut game = Game::new_with_base(
+            args.rows,
+            args.cols,
+            args.difficulty,
+            args.preview,
+            args.base,
+            rng(args.seed),
+        );
+        game.set_start_score(args.start_score);
+        game.set_neighbour_mode(args.neighbours);
+        fill_bag(&mut game, args.bag);
+        let finished = solver::solve(&mut game);
+        println!("seed: {}", args.seed);
+        println!("placements: {}", game.num_placed());
+        println!(
+            "result: {}",
+            match finished {
+                Some(game::Finished::Success) => "success",
+                Some(game::Finished::Failure) => "failure",
+                None => "undecided",
+            }
+        );
+        return Ok(ExitCode::from(exit_status(finished)));
+    }
     let mut app = App {
         game: RenderedGame {
-            state: Game::new(rng(args.seed)),
-            stone_labels: if args.wide {
-                ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"]
+            state: Game::new_with_base(
+                args.rows,
+                args.cols,
+                args.difficulty,
+                args.preview,
+                args.base,
+                rng(args.seed),
+            ),
+            stone_labels: if args.wide && !args.mini {
+                WIDE_LABELS
             } else {
-                ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]
+                PACKED_LABELS
             },
-            stone_styles: [
-                /* 0 */ Style::new().bg(Color::DarkGray).fg(Color::White),
-                /* 1 */ Style::new().bg(Color::Magenta).fg(Color::White),
-                /* 2 */ Style::new().bg(Color::Blue).fg(Color::White),
-                /* 3 */ Style::new().bg(Color::Red).fg(Color::LightYellow),
-                /* 4 */ Style::new().bg(Color::Yellow).fg(Color::Black),
-                /* 5 */ Style::new().bg(Color::Green).fg(Color::Black),
-                /* 6 */ Style::new().bg(Color::LightBlue).fg(Color::Black),
-                /* 7 */ Style::new().bg(Color::Magenta).fg(Color::Black),
-                /* 8 */ Style::new().bg(Color::DarkGray).fg(Color::Yellow),
-                /* 9 */ Style::new().bg(Color::Gray).fg(Color::Black),
-            ],
-            packed_ui: !args.wide,
+            stone_styles: match &args.theme {
+                Some(path) => theme::load_theme(path, args.palette)?,
+                None => args.palette.stone_styles(),
+            },
+            packed_ui: !args.wide || args.mini,
+            mini: args.mini,
+            cursor: None,
+            clearing: None,
+            flash: None,
+            show_sums: false,
+            show_trail: !args.no_trail,
+            marker: None,
+            coords: args.coords,
+            color: !args.no_color && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
         },
-        point: Some(Cursor::default()),
+        point: None,
+        board_area: Rect::default(),
+        scroll: (0, 0),
+        timer: Timer::default(),
         new_rng: rng,
         seed: args.seed,
+        difficulty: args.difficulty,
+        start_score: args.start_score,
+        bag: args.bag,
+        random_cursor: args.random_cursor,
+        zen: args.zen,
+        goal_clears: args.goal_clears,
+        reveal_seed: args.reveal_seed,
+        resumed: false,
+        assist: args.assist,
+        misses: 0,
+        daily,
+        confirm: !args.no_confirm,
+        skip_isolated: args.skip_isolated,
+        bell: !args.no_bell,
+        show_need: false,
+        key_repeat: args.smooth_keys.then(KeyRepeat::default),
+        focused: true,
         mode: ScreenMode::Playing,
-        help_return_mode: ScreenMode::Playing,
+        return_mode: ScreenMode::Playing,
+        notice: None,
+        notice_until: None,
+        keymap: Keymap::load()?,
+        stats: stats::Stats::load()?,
+        recorder: None,
+        replay: None,
+        moves: Some(Vec::new()),
+        playback: None,
+        demo: args.demo.then(Instant::now),
+        seed_input: String::new(),
+        redraw: false,
+        frame_interval: Duration::from_secs(1) / args.fps,
+        dirty: true,
+        text_page: 0,
     };
-    #[cfg(feature = "dev")]
-    if let Some(path) = args.board {
-        let r = std::fs::File::open(path)?;
-        let r = std::io::BufReader::new(r);
-        app.game.state.load_from_reader(r)?;
-        app.point = app.game.state.find_free_any(app.point.unwrap_or_default());
-        if app.point.is_none() {
+    app.game.state.set_start_score(args.start_score);
+    app.game.state.set_neighbour_mode(args.neighbours);
+    fill_bag(&mut app.game.state, args.bag);
+    app.point = if app.random_cursor {
+        Some(app.game.state.initial_cursor())
+    } else {
+        // ~ the top left corner may be occupied, e.g. on hard
+        app.game.state.find_free_any(Cursor::default())
+    };
+    if args.resume {
+        let path = paths::save_file().context("no location for saved games")?;
+        let r = std::fs::File::open(&path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        app.game.state = Game::load_full_from_reader(std::io::BufReader::new(r), rng(args.seed))
+            .with_context(|| format!("failed to resume from {}", path.display()))?;
+        app.point = app.game.state.find_free_any(Cursor::default());
+        app.moves = None;
+        app.resumed = true;
+        // ~ the rules the game was started with
+        app.zen = app.game.state.is_zen();
+        app.goal_clears = app.game.state.goal_clears().unwrap_or(0);
+        if app.game.state.is_finished().is_some() {
+            app.mode = ScreenMode::GameOver;
+        }
+    }
+    if let Some(path) = &args.play {
+        let replay = Replay::load(path)?;
+        let h = replay.header;
+        app.game.state =
+            Game::new_with_base(h.rows, h.cols, h.difficulty, h.preview, h.base, rng(h.seed));
+        app.game.state.set_start_score(h.start_score);
+        app.game.state.set_neighbour_mode(h.neighbours);
+        fill_bag(&mut app.game.state, h.bag);
+        if h.random_cursor {
+            // ~ for the sake of the stones drawn after it
+            app.game.state.initial_cursor();
+        }
+        app.seed = h.seed;
+        app.difficulty = h.difficulty;
+        app.start_score = h.start_score;
+        app.bag = h.bag;
+        app.random_cursor = h.random_cursor;
+        app.zen = h.zen;
+        app.goal_clears = h.goal_clears;
+        app.point = next_placement(&replay);
+        app.replay = Some(replay);
+        app.moves = None;
+        app.mode = ScreenMode::Replay;
+    }
+    if let Some(path) = &args.board {
+        if args.resume || args.play.is_some() {
+            anyhow::bail!("'--board' cannot be combined with '--resume' or '--play'");
+        }
+        // ~ "-" for the board given on stdin; in text mode, the
+        // commands follow right after it
+        let loaded = if path.as_os_str() == "-" {
+            let lines = board_lines(io::stdin().lock(), app.game.state.rows())
+                .context("failed to read the board from stdin")?;
+            app.game.state.load_from_reader(lines.as_bytes())
+        } else {
+            let r = std::fs::File::open(path)
+                .with_context(|| format!("failed to open {}", path.display()))?;
+            app.game.state.load_from_reader(io::BufReader::new(r))
+        };
+        loaded.with_context(|| format!("failed to load board from {}", path.display()))?;
+        app.point = app.game.state.find_free_any(Cursor::default());
+        app.moves = None;
+        // ~ nothing to play; most likely not what was intended
+        if let Some(finished) = app.game.state.is_finished() {
             app.mode = ScreenMode::GameOver;
+            app.notice = Some(Cow::Borrowed(match finished {
+                game::Finished::Success => "The loaded board is clear already.",
+                game::Finished::Failure => "The loaded board allows no clearing placement.",
+            }));
+        }
+    }
+    if let Some(path) = &args.record {
+        // ~ a recording doesn't capture a loaded board; nor is a game
+        // played by the demo worth recording
+        if args.resume || args.play.is_some() || args.board.is_some() || args.demo {
+            anyhow::bail!(
+                "'--record' cannot be combined with '--resume', '--play', '--board', or '--demo'"
+            );
         }
+        app.recorder = Some(replay::Recorder::create(path, &app.replay_header())?);
+    }
+    app.game.state.set_skip_isolated(app.skip_isolated);
+    app.game.state.set_wrap(!args.no_wrap);
+    app.game.state.set_zen(app.zen);
+    app.game
+        .state
+        .set_goal_clears((app.goal_clears > 0).then_some(app.goal_clears));
+    app.game
+        .state
+        .set_allow_isolated_placement(!args.refuse_isolated);
+    // ~ on stderr such that it's still around after leaving the
+    // alternate screen (and out of the way of the text mode)
+    if args.verbose && !app.resumed {
+        match app.daily {
+            Some(date) => eprintln!("seed: {} (daily puzzle of {date})", app.seed),
+            None => eprintln!("seed: {}", app.seed),
+        }
+    }
+    if args.demo && (args.play.is_some() || args.text) {
+        anyhow::bail!("'--demo' cannot be combined with '--play' or '--text'");
+    }
+    if args.text {
+        if args.play.is_some() || args.record.is_some() {
+            anyhow::bail!("'--text' cannot be combined with '--play' or '--record'");
+        }
+        let finished = text::play(
+            &mut app.game.state,
+            io::stdin().lock(),
+            &mut io::stdout().lock(),
+        )?;
+        return Ok(ExitCode::from(exit_status(finished)));
     }
     let terminal = ratatui::init();
-    let result = app.run(terminal);
+    let result = execute!(
+        io::stdout(),
+        event::EnableMouseCapture,
+        event::EnableFocusChange
+    )
+    .map_err(Into::into)
+    .and_then(|_| app.run(terminal));
+    let _ = execute!(
+        io::stdout(),
+        event::DisableFocusChange,
+        event::DisableMouseCapture
+    );
     ratatui::restore();
-    result
+    #[cfg(feature = "dev")]
+    if let Some(path) = args.dump {
+        let mut w = std::io::BufWriter::new(std::fs::File::create(path)?);
+        app.game.state.dump_to_writer(&mut w)?;
+    }
+    result.map(|_| ExitCode::SUCCESS)
 }
 
 // --------------------------------------------------------------------
@@ -69,15 +329,100 @@ struct App<R, F> {
     game: RenderedGame<R>,
     // ~ where is the current right now?
     point: Option<Cursor>,
+    // ~ where the board was rendered last; empty if it wasn't
+    board_area: Rect,
+    // ~ the offset of the part of the board shown in `board_area` if the
+    // board doesn't fit the window entirely; (columns, rows)
+    scroll: (u16, u16),
+    // ~ the time spent playing the current game
+    timer: Timer,
     // ~ function to create new rngs given a seed
     new_rng: F,
     // ~ the seed we started the current game with
     seed: u64,
+    // ~ the difficulty to start new games with
+    difficulty: game::Difficulty,
+    // ~ the number of placements to start new games with
+    start_score: usize,
+    // ~ the number of each stone in the bag to start new games with;
+    // zero for an endless stream of stones
+    bag: usize,
+    // ~ true to start new games with the cursor at a random cell
+    random_cursor: bool,
+    // ~ true to play without the chance of failure
+    zen: bool,
+    // ~ the number of stones to clear to win a game; zero to win by
+    // clearing the board
+    goal_clears: usize,
+    // ~ true to include the seed in the game-over message
+    reveal_seed: bool,
+    // ~ true while playing a game restored through `--resume`; its
+    // seed is unknown, as the save doesn't tell it
+    resumed: bool,
+    // ~ the day whose puzzle the current game is; `None` unless
+    // playing a daily puzzle
+    daily: Option<date::Date>,
+    // ~ true to ask before quitting a game in progress or starting a
+    // new one
+    confirm: bool,
+    // ~ true to have the cursor prefer free cells next to a stone
+    skip_isolated: bool,
+    // ~ true to ring the terminal's bell on placements which don't
+    // clear anything
+    bell: bool,
+    // ~ true to point out a clearing place after `ASSIST_AFTER`
+    // placements in a row which didn't clear anything; counted by
+    // `misses`
+    assist: bool,
+    misses: usize,
+    // ~ true to tell the last digit of the neighbours' sum the next
+    // stone needs to clear along with the one at the cursor
+    show_need: bool,
+    // ~ smooths the movement by held keys; `None` to move by every
+    // single key event
+    key_repeat: Option<KeyRepeat>,
+    // ~ false while the terminal is known to have lost the focus; the
+    // game is paused meanwhile
+    focused: bool,
     // ~ which screen are we in right now?
     mode: ScreenMode,
-    // ~ the mode to return to when closing the 'help' screen;
-    // maintained/set when opening the 'help' window
-    help_return_mode: ScreenMode,
+    // ~ the mode to return to when closing the 'help' or 'stats'
+    // screen; maintained/set when opening the screen
+    return_mode: ScreenMode,
+    // ~ a message to display instead of the hint line until the
+    // next key press
+    notice: Option<Cow<'static, str>>,
+    // ~ when the notice goes away on its own; `None` for one to stay
+    // until the next key press
+    notice_until: Option<Instant>,
+    // ~ the key bindings
+    keymap: Keymap,
+    // ~ the lifetime statistics over all finished games
+    stats: stats::Stats,
+    // ~ where to record the moves of the game to, if at all
+    recorder: Option<replay::Recorder>,
+    // ~ the recorded game being replayed, if any
+    replay: Option<Replay>,
+    // ~ the moves of the current game so far; `None` if the game
+    // cannot be replayed from its seed (e.g. a resumed one)
+    moves: Option<Vec<Step>>,
+    // ~ when the last move of a game being played back on its own
+    // was replayed; `None` unless playing back
+    playback: Option<Instant>,
+    // ~ when the game playing itself made its last move; `None`
+    // unless in the demo mode
+    demo: Option<Instant>,
+    // ~ the seed typed in on the seed entry screen
+    seed_input: String,
+    // ~ true to clear the terminal before drawing the next frame
+    redraw: bool,
+    // ~ the minimum time between two frames; see `--fps`
+    frame_interval: Duration,
+    // ~ true if the screen changed since drawing the last frame
+    dirty: bool,
+    // ~ the number of lines of text shown by the 'help' or 'stats'
+    // screen when drawn last; scrolled by a page
+    text_page: u16,
 }
 
 #[derive(Clone, Copy)]
@@ -86,29 +431,140 @@ enum ScreenMode {
     GameOver,
     // Maintains the current scroll position
     Help(u16),
+    // Maintains the current scroll position
+    Keys(u16),
+    // Maintains the current scroll position
+    Stats(u16),
+    // Awaits the answer to the question before acting on it
+    Confirm(Question),
+    // Steps through a recorded game
+    Replay,
+    // Edits the seed of a new game to start; maintained in
+    // `App::seed_input`
+    SeedEntry,
     Exit,
 }
 
+/// The questions to be confirmed before acting on them
+#[derive(Clone, Copy)]
+enum Question {
+    NewGame,
+    Restart,
+    Quit,
+}
+
+impl Question {
+    fn prompt(self) -> &'static str {
+        match self {
+            Question::NewGame => "Start new game? (y/n)",
+            Question::Restart => "Abandon this game for a new one? (y/n)",
+            Question::Quit => "Quit? (y/n)",
+        }
+    }
+
+    // ~ the screen the question was asked on
+    fn return_mode(self) -> ScreenMode {
+        match self {
+            Question::NewGame => ScreenMode::GameOver,
+            Question::Restart | Question::Quit => ScreenMode::Playing,
+        }
+    }
+}
+
 impl<R: Rng, F: Fn(u64) -> R> App<R, F> {
+    // ~ the native driver of the app: draws a frame, waits for input
+    // (or the next frame to be due), and steps the app until it exits
     fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
-        while !matches!(self.mode, ScreenMode::Exit) {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.handle_events()?;
+        let mut event = None;
+        // ~ when the last frame was drawn; frames are drawn only if
+        // something changed and no more often than `frame_interval`
+        let mut drawn: Option<Instant> = None;
+        while self.step(event.take()) {
+            let due = drawn.is_none_or(|t| t.elapsed() >= self.frame_interval);
+            if self.dirty && due {
+                if std::mem::take(&mut self.redraw) {
+                    terminal.clear()?;
+                }
+                terminal.draw(|frame| {
+                    self.draw(frame);
+                    if !self.game.color {
+                        strip_colors(frame.buffer_mut());
+                    }
+                })?;
+                self.dirty = false;
+                drawn = Some(Instant::now());
+            }
+            let timeout = match drawn {
+                Some(t) if self.dirty => self.frame_interval.saturating_sub(t.elapsed()),
+                _ => self.frame_timeout(),
+            };
+            event = event::poll(timeout)?.then(event::read).transpose()?;
         }
         Ok(())
     }
 
+    // ~ advances the app by a single frame applying the given input
+    // `event`, if any; returns whether to keep running.  drawing the
+    // frame is up to the caller (see `draw`) such that the loop can be
+    // owned by a platform specific driver.
+    fn step(&mut self, event: Option<event::Event>) -> bool {
+        let event_handled = event.is_some();
+        if let Some(event) = event {
+            self.handle_event(event);
+        }
+        // ~ the clock ticks only while actually playing; paused while
+        // reading the help (or away from the terminal) and frozen once
+        // the game is over
+        self.timer
+            .set_running(self.focused && matches!(self.mode, ScreenMode::Playing));
+        // ~ the screen changes with any input, by a running animation,
+        // and by the ticking clock
+        self.dirty |= event_handled
+            || self.game.clearing.is_some()
+            || self.game.flash.is_some()
+            || self.timer.running_since.is_some();
+        if self.game.clearing.as_ref().is_some_and(Clearing::is_done) {
+            self.game.clearing = None;
+        }
+        if self.game.flash.as_ref().is_some_and(Flash::is_done) {
+            self.game.flash = None;
+        }
+        if self
+            .notice_until
+            .is_some_and(|until| Instant::now() >= until)
+        {
+            self.notice = None;
+            self.notice_until = None;
+            self.dirty = true;
+        }
+        if let Some(last) = self.playback
+            && self.game.clearing.is_none()
+            && last.elapsed() >= PLAYBACK_STEP
+        {
+            self.replay_step();
+            self.playback = matches!(self.mode, ScreenMode::Replay).then(Instant::now);
+            self.dirty = true;
+        }
+        if let Some(last) = self.demo
+            && self.game.clearing.is_none()
+            && last.elapsed() >= DEMO_STEP
+        {
+            self.demo_step();
+            self.demo = Some(Instant::now());
+            self.dirty = true;
+        }
+        !matches!(self.mode, ScreenMode::Exit)
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
-        let height = self.game.state.rows() as u16 + 2;
-        let width = self.game.state.cols() as u16 * 2
-            + if self.game.packed_ui {
-                2 + 6 /* borders (left, right) + "nexts" area */
-            } else {
-                1 + 5 /* one border; right omitted (packed) + "nexts" area (packed) */
-            };
+        let (width, height) = self.game.size();
 
         let frame_area = frame.area();
-        if frame_area.width < width || frame_area.height < height {
+        let fits = frame_area.width >= width && frame_area.height >= height;
+        // ~ unless the board fits entirely, only a part of it is shown
+        // above the hint line; which must hold at least a single row
+        if !fits && (frame_area.width < 4 || frame_area.height < 4) {
+            self.board_area = Rect::default();
             let [area] = Layout::vertical([Constraint::Length(1)])
                 .flex(Flex::Center)
                 .areas(frame_area);
@@ -125,266 +581,1507 @@ impl<R: Rng, F: Fn(u64) -> R> App<R, F> {
 
         // ~ render the hint line before other widgets so it get overdrawn if
         // space is tight
+        let keymap = &self.keymap;
+        let hint = |action, label| key_hint(keymap, action, label, Style::new());
         let hint_line = match self.mode {
-            ScreenMode::GameOver => Line::from_iter([
-                Span::raw(" "),
-                Span::raw("q").fg(Color::Magenta),
-                Span::raw("uit | "),
-                Span::raw("n").fg(Color::Magenta),
-                Span::raw("ew game | "),
-                Span::raw("h").fg(Color::Magenta),
-                Span::raw("elp"),
+            ScreenMode::GameOver => hint_line([
+                hint(Action::Quit, "quit"),
+                hint(Action::NewGame, "new game"),
+                hint(Action::EnterSeed, "go to seed"),
+                hint(Action::Stats, "Stats"),
+                hint(Action::Help, "help"),
             ]),
-            ScreenMode::Help(_) => Line::from_iter([
+            ScreenMode::SeedEntry => Line::from_iter([
                 Span::raw(" "),
-                Span::raw("q").fg(Color::Magenta),
-                Span::raw("/"),
+                Span::raw("<enter>").fg(Color::Magenta),
+                Span::raw(" start | "),
                 Span::raw("esc").fg(Color::Magenta),
-                Span::raw(" close"),
+                Span::raw(" cancel"),
             ]),
-            _ => Line::from_iter([
+            ScreenMode::Confirm(_) => Line::from_iter([
                 Span::raw(" "),
-                Span::raw("q").fg(Color::Magenta).bold(),
-                Span::raw("uit | "),
-                Span::raw("h").fg(Color::Magenta).bold(),
-                Span::raw("elp | ←↑↓→ <space>"),
+                Span::raw("y").fg(Color::Magenta),
+                Span::raw("es | "),
+                Span::raw("n").fg(Color::Magenta),
+                Span::raw("o"),
             ]),
+            ScreenMode::Playing if self.demo.is_some() => {
+                Line::raw(" demo | press any key to play")
+            }
+            ScreenMode::Replay if self.playback.is_some() => {
+                let (pos, len) = self.replay.as_ref().map_or((0, 0), Replay::progress);
+                Line::raw(format!(" press any key to skip | step {pos}/{len}"))
+            }
+            ScreenMode::Replay => {
+                let (pos, len) = self.replay.as_ref().map_or((0, 0), Replay::progress);
+                hint_line([
+                    hint(Action::Quit, "quit"),
+                    hint(Action::Place, &format!("step {pos}/{len}")),
+                ])
+            }
+            ScreenMode::Help(_) | ScreenMode::Keys(_) | ScreenMode::Stats(_) => {
+                let mut close = vec![Span::raw("esc").fg(Color::Magenta), Span::raw(" close")];
+                if let Some(quit) = keymap.keys_for(Action::Quit).first() {
+                    close.splice(
+                        ..0,
+                        [Span::raw(quit.label()).fg(Color::Magenta), Span::raw("/")],
+                    );
+                }
+                let scroll = [Action::MoveNorth, Action::MoveSouth]
+                    .into_iter()
+                    .filter_map(|a| keymap.keys_for(a).first().map(keys::Chord::label))
+                    .collect::<String>();
+                hint_line([
+                    Some(close),
+                    Some(vec![
+                        Span::raw(format!("{scroll} ")),
+                        Span::raw("pgup").fg(Color::Magenta),
+                        Span::raw("/"),
+                        Span::raw("pgdn").fg(Color::Magenta),
+                        Span::raw(" "),
+                        Span::raw("home").fg(Color::Magenta),
+                        Span::raw("/"),
+                        Span::raw("end").fg(Color::Magenta),
+                        Span::raw(" scroll"),
+                    ]),
+                    hint(Action::Keys, "keys").filter(|_| matches!(self.mode, ScreenMode::Help(_))),
+                ])
+            }
+            _ => {
+                let hint = |action, label| key_hint(keymap, action, label, Style::new().bold());
+                // ~ the keys to move around and to place the next stone;
+                // the arrows go without spaces in between
+                let label = |a| keymap.keys_for(a).first().map(keys::Chord::label);
+                let moves = [
+                    Action::MoveWest,
+                    Action::MoveNorth,
+                    Action::MoveSouth,
+                    Action::MoveEast,
+                ]
+                .into_iter()
+                .filter_map(label)
+                .collect::<Vec<_>>();
+                let arrows = moves
+                    .iter()
+                    .all(|m| ["←", "↑", "↓", "→"].contains(&m.as_str()));
+                let mut moves = moves.join(if arrows { "" } else { " " });
+                if let Some(place) = label(Action::Place) {
+                    moves = format!("{moves} {place}").trim_start().to_owned();
+                }
+                hint_line([
+                    hint(Action::Quit, "quit"),
+                    hint(Action::Help, "help"),
+                    hint(Action::Stats, "Stats"),
+                    hint(Action::Undo, "undo"),
+                    Some(vec![Span::raw(moves)]),
+                ])
+            }
+        };
+        let hint_line = match &self.notice {
+            Some(notice) => Line::from_iter([Span::raw(" "), Span::raw(notice.as_ref())]),
+            None => hint_line,
         };
-        frame.render_widget(hint_line.fg(Color::DarkGray), hint_line_rect);
 
-        let board_area = {
-            Rect {
+        // ~ the elapsed time at the right end of the hint line; preceded
+        // by the seed once the game is over
+        let time = {
+            let secs = self.timer.elapsed().as_secs();
+            format!("{:02}:{:02}", secs / 60, secs % 60)
+        };
+        let mut b = itoa::Buffer::new();
+        let seed_info = match self.mode {
+            ScreenMode::Playing | ScreenMode::GameOver | ScreenMode::Replay
+                if self.game.state.is_finished().is_some() && !self.resumed =>
+            {
+                Some(match self.daily {
+                    Some(date) => Cow::Owned(format!("daily {date} | {}", self.seed)),
+                    None => Cow::Borrowed(b.format(self.seed)),
+                })
+            }
+            _ => None,
+        };
+        let info_line = match seed_info {
+            Some(seed) => Line::from_iter([Span::raw(seed), Span::raw(" "), Span::raw(time)]),
+            None => Line::raw(time),
+        };
+        // ~ side by side such that neither overwrites the other; the
+        // hint gets cut short if space is tight
+        let [hint_rect, info_rect] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(info_line.width() as u16),
+        ])
+        .spacing(1)
+        .areas(hint_line_rect);
+        frame.render_widget(hint_line.fg(Color::DarkGray), hint_rect);
+        frame.render_widget(info_line.right_aligned().fg(Color::DarkGray), info_rect);
+
+        self.game.cursor = match self.mode {
+            ScreenMode::Playing | ScreenMode::Replay => self.point,
+            _ => None,
+        };
+        // ~ the marker only ever rests on a free cell
+        if let Some(m) = self.game.marker
+            && self.game.state.cell(m).is_some()
+        {
+            self.game.marker = None;
+        }
+        let board_area = if fits {
+            self.scroll = (0, 0);
+            let board_area = Rect {
                 x: frame_area.x + (frame_area.width - width) / 2,
                 y: frame_area.y + (frame_area.height - height) / 2,
                 width,
                 height,
+            };
+            frame.render_widget(&self.game, board_area);
+            board_area
+        } else {
+            let (view_width, view_height) = (
+                width.min(frame_area.width),
+                height.min(frame_area.height - 1),
+            );
+            // ~ keep the cursor's cell visible along with the cells
+            // (or border) around it
+            if let Some(point) = self.point {
+                let (ox, oy) = self.game.board_offset();
+                let (x, y) = (
+                    ox + point.x as u16 * self.game.cell_step(),
+                    oy + point.y as u16,
+                );
+                self.scroll = (
+                    scroll_to(self.scroll.0, x, x + 4, view_width, width),
+                    scroll_to(self.scroll.1, y, y + 3, view_height, height),
+                );
             }
+            let board_area = Rect {
+                x: frame_area.x + (frame_area.width - view_width) / 2,
+                y: frame_area.y + (frame_area.height - 1 - view_height) / 2,
+                width: view_width,
+                height: view_height,
+            };
+            // ~ render the whole board off-screen and copy the visible
+            // part of it
+            let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+            (&self.game).render(buf.area, &mut buf);
+            for y in 0..view_height {
+                for x in 0..view_width {
+                    frame.buffer_mut()[(board_area.x + x, board_area.y + y)] =
+                        buf[(self.scroll.0 + x, self.scroll.1 + y)].clone();
+                }
+            }
+            board_area
         };
-        frame.render_widget(&self.game, board_area);
+        self.board_area = board_area;
+        // ~ paused while the terminal is out of focus
+        if !self.focused {
+            frame
+                .buffer_mut()
+                .set_style(board_area, Style::new().add_modifier(Modifier::DIM));
+        }
 
         match self.mode {
-            ScreenMode::Playing | ScreenMode::GameOver => {
+            ScreenMode::Playing | ScreenMode::GameOver | ScreenMode::Replay => {
                 if let Some(state) = self.game.state.is_finished() {
                     // ~ congrats / boo message
-                    let s = match state {
+                    let mut s = match state {
                         game::Finished::Success => Cow::Owned(format!(
                             "Congratulations!\n\nYou made it with {} placements only! 😎",
                             self.game.state.num_placed(),
                         )),
-                        game::Finished::Failure => {
-                            Cow::Borrowed("Too bad, no more placements possible!\n\nGame over! 😕")
-                        }
+                        game::Finished::Failure => Cow::Owned(format!(
+                            "Too bad, no more placements possible!\n\n\
+                             {} numbers left summing to {}.\n\nGame over! 😕",
+                            self.game.state.num_remaining(),
+                            self.game.state.remaining_sum(),
+                        )),
                     };
-                    // ~ make the row above and below blank as well
-                    let mut area = Rect {
-                        x: frame_area.x,
-                        y: frame_area.y + (frame_area.height / 2) - 2 - 1,
-                        width: frame_area.width,
-                        height: 5,
+                    let histogram = stone_histogram(&self.game.state);
+                    s = Cow::Owned(format!("{s}\n\n{histogram}"));
+                    if let Some(best) = self.daily.and_then(|d| self.stats.daily.get(&d.seed())) {
+                        s = Cow::Owned(format!("{s}\n\nYour best on today's puzzle: {best}"));
+                    }
+                    if self.reveal_seed && !self.resumed {
+                        s = Cow::Owned(format!("{s}\n\nSeed: {}", self.seed));
+                        if let Some(date) = self.daily {
+                            s = Cow::Owned(format!("{s} (daily puzzle of {date})"));
+                        }
+                    }
+                    render_message(frame, &s);
+                } else {
+                    // ~ how the placements so far compare to the par;
+                    // right below the board if there's room for it
+                    let par_line = Rect {
+                        y: board_area.y + board_area.height,
+                        height: 1,
+                        ..board_area
                     };
-                    frame.render_widget(Clear, area);
-                    // ~ shrink the area
-                    area.y += 1;
-                    frame.render_widget(Paragraph::new(s).centered(), area);
-
-                    // ~ render / reveal the seed
-                    {
-                        let mut b = itoa::Buffer::new();
-                        let seed = b.format(self.seed);
+                    if par_line.y < hint_line_rect.y {
+                        let par = par_indicator(&self.game.state);
+                        let par_width = (par.width() as u16).min(par_line.width);
                         frame.render_widget(
-                            Line::raw(seed).right_aligned().fg(Color::DarkGray),
+                            par,
                             Rect {
-                                x: hint_line_rect.width - seed.len() as u16,
-                                y: hint_line_rect.y,
-                                width: seed.len() as u16,
-                                height: 1,
+                                x: par_line.x + par_line.width - par_width,
+                                width: par_width,
+                                ..par_line
                             },
                         );
+                        // ~ to the left of the par; a column apart
+                        let rest = Rect {
+                            width: (par_line.width - par_width).saturating_sub(1),
+                            ..par_line
+                        };
+                        if let Some(marker) = self.game.marker {
+                            frame.render_widget(marker_outcome(&self.game.state, marker), rest);
+                        } else if self.show_need
+                            && let Some(need) =
+                                self.point.and_then(|p| need_have(&self.game.state, p))
+                        {
+                            frame.render_widget(need, rest);
+                        }
                     }
-                } else if let Some(point) = self.point {
-                    if self.game.packed_ui {
-                        frame.set_cursor_position(Position {
-                            x: board_area.x + 1 + point.x as u16 * 2,
-                            y: board_area.y + 1 + point.y as u16,
-                        });
-                    } else {
-                        frame.buffer_mut()[Position {
-                            x: board_area.x + 1 + point.x as u16 * 2,
-                            y: board_area.y + 1 + point.y as u16,
-                        }]
-                        .set_fg(Color::Reset)
-                        .set_char('＃');
-                        // .set_char('＠');
-                        // .set_char('ｏ');
+                    // ~ the stones left on the board and those left in a
+                    // finite bag; right above the board if there's room
+                    // for it
+                    if board_area.y > frame_area.y {
+                        let top_line = Rect {
+                            y: board_area.y - 1,
+                            height: 1,
+                            ..board_area
+                        };
+                        let state = &self.game.state;
+                        let progress = match state.goal_clears() {
+                            Some(goal) => format!("cleared {}/{goal}", state.num_cleared()),
+                            None => format!("{} left", state.num_remaining()),
+                        };
+                        frame.render_widget(Line::raw(progress), top_line);
+                        if let Some(n) = self.game.state.bag_len() {
+                            frame.render_widget(
+                                Line::raw(format!("bag {n}")).right_aligned(),
+                                top_line,
+                            );
+                        }
+                    }
+                    if let Some(point) = self.point {
+                        let (ox, oy) = self.game.board_offset();
+                        let position = Position {
+                            x: board_area.x + ox + 1 + point.x as u16 * self.game.cell_step()
+                                - self.scroll.0,
+                            y: board_area.y + oy + 1 + point.y as u16 - self.scroll.1,
+                        };
+                        if self.game.packed_ui {
+                            frame.set_cursor_position(position);
+                        } else {
+                            frame.buffer_mut()[position]
+                                .set_fg(Color::Reset)
+                                .set_char('＃');
+                            // .set_char('＠');
+                            // .set_char('ｏ');
+                        }
                     }
                 }
             }
             ScreenMode::Help(ref mut scroll) => {
-                frame.render_stateful_widget(
-                    Help,
-                    Rect {
-                        x: frame_area.x,
-                        y: frame_area.y,
-                        width: frame_area.width,
-                        height: frame_area.height.saturating_sub(1),
-                    },
-                    scroll,
+                self.text_page = render_text_screen(frame, HELP_TITLE, HELP_TEXT, scroll);
+            }
+            ScreenMode::Keys(ref mut scroll) => {
+                let text = format!("\n{}\n", self.keymap.describe());
+                self.text_page = render_text_screen(frame, " Keys ", &text, scroll);
+            }
+            ScreenMode::Stats(ref mut scroll) => {
+                let text = format!("\n{}\n", self.stats);
+                self.text_page = render_text_screen(frame, " Statistics ", &text, scroll);
+            }
+            ScreenMode::Confirm(question) => {
+                render_message(frame, question.prompt());
+            }
+            ScreenMode::SeedEntry => {
+                render_message(
+                    frame,
+                    &format!("Seed of the game to play:\n\n{}_", self.seed_input),
                 );
             }
             ScreenMode::Exit => {}
         }
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
+    // ~ the time until the next frame is due even without any input;
+    // keeps the clock ticking and animations going, but never less
+    // than `frame_interval`.  long on a static screen.
+    fn frame_timeout(&self) -> Duration {
+        let timeout = if self.game.clearing.is_some() {
+            Clearing::FRAME
+        } else if let Some(flash) = &self.game.flash {
+            flash.duration.saturating_sub(flash.started.elapsed())
+        } else if self.playback.is_some() {
+            PLAYBACK_STEP
+        } else if self.demo.is_some() {
+            DEMO_STEP
+        } else {
+            let mut timeout = IDLE_TIMEOUT;
+            // ~ the clock shows the seconds; wake up for the next one
+            if self.timer.running_since.is_some() {
+                let subsec = Duration::from_nanos(self.timer.elapsed().subsec_nanos().into());
+                timeout = timeout.min(Duration::from_secs(1) - subsec);
+            }
+            if let Some(until) = self.notice_until {
+                timeout = timeout.min(until.saturating_duration_since(Instant::now()));
+            }
+            timeout
+        };
+        timeout.max(self.frame_interval)
+    }
+
+    fn handle_event(&mut self, event: event::Event) {
+        // ~ any input skips a running animation; but for the flash
+        // pointing out a clearing place which is meant to be followed
+        if matches!(event, event::Event::Key(_) | event::Event::Mouse(_)) {
+            self.game.clearing = None;
+            self.game.flash = self.game.flash.take().filter(Flash::is_assist);
+        }
+        match event {
             event::Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
                 self.handle_key_event(key_event)
             }
+            event::Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
+            event::Event::Resize(..) => self.handle_resize(),
+            event::Event::FocusGained => self.focused = true,
+            event::Event::FocusLost => self.focused = false,
+            _ => {}
+        }
+    }
+
+    fn handle_resize(&mut self) {
+        // ~ the board moves along with the window; ignore clicks until
+        // it has been rendered anew
+        self.board_area = Rect::default();
+        // ~ make sure the cursor ends up on a free cell
+        if let Some(point) = self.point {
+            let (rows, cols) = (self.game.state.rows(), self.game.state.cols());
+            let point = Cursor {
+                x: point.x.min(cols as u8 - 1),
+                y: point.y.min(rows as u8 - 1),
+            };
+            self.point = if self.game.state.cell(point).is_none() {
+                Some(point)
+            } else {
+                self.game.state.find_free_any(point)
+            };
+        }
+        self.redraw = true;
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent) {
+        if !matches!(self.mode, ScreenMode::Playing) {
+            return;
+        }
+        if self.demo.is_some() {
+            if matches!(event.kind, MouseEventKind::Down(_)) {
+                self.stop_demo();
+            }
+            return;
+        }
+        // ~ map the click back onto the board; every cell is two
+        // columns wide (but in the mini UI)
+        let (rows, cols) = (self.game.state.rows(), self.game.state.cols());
+        let (ox, oy) = self.game.board_offset();
+        let (Some(x), Some(y)) = (
+            (event.column + self.scroll.0).checked_sub(self.board_area.x + ox + 1),
+            (event.row + self.scroll.1).checked_sub(self.board_area.y + oy + 1),
+        ) else {
+            return;
+        };
+        if !self
+            .board_area
+            .contains(Position::new(event.column, event.row))
+        {
+            return;
+        }
+        let (x, y) = (x as usize / self.game.cell_step() as usize, y as usize);
+        if self.board_area.is_empty() || x >= cols || y >= rows {
+            return;
+        }
+        if self.game.state.get(y, x).is_some() {
+            return;
+        }
+        let point = Cursor {
+            x: x as u8,
+            y: y as u8,
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.point = Some(point);
+                self.place_next();
+            }
+            MouseEventKind::Down(MouseButton::Right) => {
+                self.point = Some(point);
+            }
             _ => {}
         }
-        Ok(())
     }
 
     fn handle_key_event(&mut self, event: KeyEvent) {
+        self.notice = None;
+        self.notice_until = None;
+        // ~ any key skips to the end of a game played back on its own
+        if self.playback.take().is_some() {
+            while matches!(self.mode, ScreenMode::Replay) && self.replay_progress() {
+                self.replay_step();
+            }
+            self.game.clearing = None;
+            self.game.flash = None;
+            return;
+        }
+        // ~ as does it stop the game playing itself
+        if self.demo.is_some() {
+            self.stop_demo();
+            return;
+        }
+        // ~ thin out the burst of events of a held movement key
+        if matches!(self.mode, ScreenMode::Playing)
+            && let Some(action) = self.keymap.action(event)
+            && let Some(repeat) = &mut self.key_repeat
+            && !repeat.accept(action, Instant::now())
+        {
+            return;
+        }
         match self.mode {
-            ScreenMode::Playing => match event.code {
-                KeyCode::Char('q') => {
-                    self.mode = ScreenMode::Exit;
+            ScreenMode::Playing => match self.keymap.action(event) {
+                Some(Action::Quit) => self.ask(Question::Quit),
+                Some(Action::Help) => self.open_screen(ScreenMode::Help(0)),
+                Some(Action::Keys) => self.open_screen(ScreenMode::Keys(0)),
+                Some(Action::Stats) => self.open_screen(ScreenMode::Stats(0)),
+                Some(Action::MoveNorth) => self.move_cursor(game::Direction::North),
+                Some(Action::MoveSouth) => self.move_cursor(game::Direction::South),
+                Some(Action::MoveEast) => self.move_cursor(game::Direction::East),
+                Some(Action::MoveWest) => self.move_cursor(game::Direction::West),
+                Some(Action::Save) => {
+                    self.notice = Some(match self.save() {
+                        Ok(()) => Cow::Borrowed("Game saved."),
+                        Err(e) => Cow::Owned(format!("Failed to save game: {e:#}")),
+                    });
                 }
-                KeyCode::Char('h') => {
-                    self.help_return_mode = self.mode;
-                    self.mode = ScreenMode::Help(0);
-                }
-                KeyCode::Char('p') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.move_cursor(game::Direction::North)
+                Some(Action::Undo) => {
+                    if let Some(point) = self.game.state.undo() {
+                        self.record(Step::Undo);
+                        self.point = self.game.state.find_free_any(point);
+                        // ~ the misses are taken back along with the
+                        // placement; as is the place pointed out
+                        self.misses = 0;
+                        self.game.flash = None;
+                    }
                 }
-                KeyCode::Up => self.move_cursor(game::Direction::North),
-                KeyCode::Char('n') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.move_cursor(game::Direction::South);
+                Some(Action::Redo) => {
+                    if let Some(point) = self.game.state.redo() {
+                        self.record(Step::Redo);
+                        self.point = self.game.state.find_free_any(point);
+                    }
+                    if self.game.state.is_finished().is_some() {
+                        self.game_over();
+                    }
                 }
-                KeyCode::Down => self.move_cursor(game::Direction::South),
-                KeyCode::Char('f') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.move_cursor(game::Direction::East)
+                Some(Action::Hint) => match self.game.state.find_hint() {
+                    Some(point) => self.point = Some(point),
+                    None => self.notice = Some(Cow::Borrowed("No clearing placement available.")),
+                },
+                Some(Action::Target) => {
+                    match self
+                        .point
+                        .and_then(|p| self.game.state.find_nearest_clearing(p))
+                    {
+                        Some(point) => self.point = Some(point),
+                        None => self.notice = Some(Cow::Borrowed("No clears available.")),
+                    }
                 }
-                KeyCode::Right => self.move_cursor(game::Direction::East),
-                KeyCode::Char('b') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.move_cursor(game::Direction::West);
+                Some(Action::Center) => {
+                    let state = &self.game.state;
+                    let center = Cursor {
+                        x: (state.cols() / 2) as u8,
+                        y: (state.rows() / 2) as u8,
+                    };
+                    self.point = state.find_free_any(center);
                 }
-                KeyCode::Left => {
-                    self.move_cursor(game::Direction::West);
+                Some(Action::Sums) => self.game.show_sums = !self.game.show_sums,
+                Some(Action::Trail) => self.game.show_trail = !self.game.show_trail,
+                Some(Action::Need) => self.show_need = !self.show_need,
+                Some(Action::Wide) => {
+                    self.game.set_wide(self.game.packed_ui);
+                    self.redraw = true;
                 }
-                KeyCode::Char(' ') => {
-                    if let Some(point) = self.point {
-                        if self.game.state.place_next(point) {
-                            self.point = self.game.state.find_free_any(point);
-                        }
-                        if self.game.state.is_finished().is_some() {
-                            self.mode = ScreenMode::GameOver;
-                        }
-                    }
+                Some(Action::Mark) => {
+                    // ~ drop the marker at the cursor; or take it away
+                    // if it's there already
+                    self.game.marker = match self.game.marker {
+                        Some(marker) if Some(marker) == self.point => None,
+                        _ => self.point,
+                    };
                 }
-                _ => {}
+                Some(Action::Place) => self.place_next(),
+                // ~ an abandoned game doesn't count towards the
+                // statistics
+                Some(Action::Restart) => self.ask(Question::Restart),
+                Some(Action::NewGame | Action::EnterSeed) | None => {}
             },
-            ScreenMode::GameOver => match event.code {
-                KeyCode::Char('q') => {
+            ScreenMode::GameOver => match self.keymap.action(event) {
+                Some(Action::Quit) => {
                     self.mode = ScreenMode::Exit;
                 }
-                KeyCode::Char('h') => {
-                    self.help_return_mode = self.mode;
-                    self.mode = ScreenMode::Help(0);
-                }
-                KeyCode::Char('n') => {
-                    self.seed = self.game.state.rng().random();
-                    self.game.state = Game::new((self.new_rng)(self.seed));
-                    self.point = Some(Cursor::default());
-                    self.mode = ScreenMode::Playing;
+                Some(Action::Help) => self.open_screen(ScreenMode::Help(0)),
+                Some(Action::Keys) => self.open_screen(ScreenMode::Keys(0)),
+                Some(Action::Stats) => self.open_screen(ScreenMode::Stats(0)),
+                Some(Action::NewGame) => self.ask(Question::NewGame),
+                Some(Action::EnterSeed) => {
+                    self.seed_input.clear();
+                    self.mode = ScreenMode::SeedEntry;
                 }
                 _ => {}
             },
-            ScreenMode::Help(scroll) => match event.code {
-                KeyCode::Char('q') | KeyCode::Esc => {
-                    self.mode = self.help_return_mode;
-                }
-                KeyCode::Char('p') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.mode = ScreenMode::Help(scroll.saturating_sub(1));
+            ScreenMode::SeedEntry => match event.code {
+                KeyCode::Enter => match self.seed_input.parse() {
+                    Ok(seed) => self.new_game_with_seed(seed),
+                    Err(_) => {
+                        self.notice = Some(Cow::Owned(format!(
+                            "Not a seed: {:?}; expected a number from 0 to {}.",
+                            self.seed_input,
+                            u64::MAX
+                        )));
+                    }
+                },
+                KeyCode::Esc => self.mode = ScreenMode::GameOver,
+                KeyCode::Backspace => {
+                    self.seed_input.pop();
                 }
-                KeyCode::Up => {
-                    self.mode = ScreenMode::Help(scroll.saturating_sub(1));
+                // ~ just typed characters; not the likes of ctrl-c
+                KeyCode::Char(c)
+                    if self.seed_input.len() < 24
+                        && (event.modifiers - KeyModifiers::SHIFT).is_empty() =>
+                {
+                    self.seed_input.push(c)
                 }
-                KeyCode::Char('n') if event.modifiers == KeyModifiers::CONTROL => {
-                    self.mode = ScreenMode::Help(scroll.saturating_add(1));
+                _ => {}
+            },
+            ScreenMode::Replay => match self.keymap.action(event) {
+                Some(Action::Quit) => {
+                    self.mode = ScreenMode::Exit;
                 }
-                KeyCode::Down => {
-                    self.mode = ScreenMode::Help(scroll.saturating_add(1));
+                Some(Action::Help) => self.open_screen(ScreenMode::Help(0)),
+                Some(Action::Keys) => self.open_screen(ScreenMode::Keys(0)),
+                Some(Action::Place | Action::MoveEast) => self.replay_step(),
+                _ => {}
+            },
+            ScreenMode::Confirm(question) => match event.code {
+                KeyCode::Char('y') => self.answer(question),
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.mode = question.return_mode();
                 }
                 _ => {}
             },
+            ScreenMode::Help(ref mut scroll)
+            | ScreenMode::Keys(ref mut scroll)
+            | ScreenMode::Stats(ref mut scroll) => {
+                match (event.code, self.keymap.action(event)) {
+                    (KeyCode::Esc, _) | (_, Some(Action::Quit)) => {
+                        self.mode = self.return_mode;
+                    }
+                    (_, Some(Action::Keys)) => self.mode = ScreenMode::Keys(0),
+                    (_, Some(Action::MoveNorth)) => *scroll = scroll.saturating_sub(1),
+                    (_, Some(Action::MoveSouth)) => *scroll = scroll.saturating_add(1),
+                    (KeyCode::PageUp, _) => *scroll = scroll.saturating_sub(self.text_page),
+                    (KeyCode::PageDown, _) => *scroll = scroll.saturating_add(self.text_page),
+                    (KeyCode::Home, _) => *scroll = 0,
+                    // ~ clamped to the last page when rendered
+                    (KeyCode::End, _) => *scroll = u16::MAX,
+                    _ => {}
+                }
+            }
             ScreenMode::Exit => {}
         }
     }
 
-    fn move_cursor(&mut self, direction: game::Direction) {
+    // ~ places the next stone at the current cursor position
+    fn place_next(&mut self) {
         if let Some(point) = self.point {
-            self.point = self.game.state.find_free_next(point, direction);
+            match self.place_at(point) {
+                PlaceOutcome::Refused => {
+                    self.notice = Some(Cow::Borrowed(if self.game.state.cell(point).is_some() {
+                        "The place is taken; move to a free one."
+                    } else {
+                        "Nothing around to clear; place next to a number."
+                    }));
+                    return;
+                }
+                PlaceOutcome::Occupied(p) => {
+                    self.point = self.game.state.find_free_any(point);
+                    if self.bell {
+                        // ~ a missed bell is no reason to bother
+                        let _ = execute!(io::stdout(), style::Print('\x07'));
+                    }
+                    self.notice = Some(Cow::Owned(format!("Placed {} → stays.", p.stone)));
+                    self.misses += 1;
+                    if self.assist
+                        && self.misses >= ASSIST_AFTER
+                        && let Some(target) = self.game.state.find_nearest_clearing(point)
+                    {
+                        self.game.flash = Some(Flash {
+                            cell: target,
+                            started: Instant::now(),
+                            duration: Flash::ASSIST_DURATION,
+                        });
+                        self.notice = Some(Cow::Owned(format!(
+                            "Placed {} → stays. Try the flashing place!",
+                            p.stone
+                        )));
+                    }
+                }
+                PlaceOutcome::Cleared(p) => {
+                    self.misses = 0;
+                    self.notice = Some(Cow::Owned(format!(
+                        "Placed {} → cleared {} {} (sum {}).",
+                        p.stone,
+                        p.cleared.len(),
+                        if p.cleared.len() == 1 {
+                            "number"
+                        } else {
+                            "numbers"
+                        },
+                        p.sum
+                    )));
+                }
+            }
+            self.notice_until = Some(Instant::now() + STATUS_DURATION);
+            self.record(Step::Place(point));
+            if self.game.state.is_finished().is_some() {
+                self.game_over();
+            }
         }
     }
-}
-
-// --------------------------------------------------------------------
 
-struct RenderedGame<R> {
-    state: Game<R>,
-    stone_labels: [&'static str; game::NUM_STONES],
-    stone_styles: [Style; game::NUM_STONES],
-    // ~ true to "pack / cram / squeeze" the UI a bit; used in
-    // non-wide mode to cut back on non-elegant visual "gaps"
-    packed_ui: bool,
-}
+    // ~ places the next stone at the (free) `point` and animates the
+    // clearing of its neighbours, if any, or flashes the occupied cell
+    fn place_at(&mut self, point: Cursor) -> PlaceOutcome {
+        let outcome = self.game.state.place_next(point);
+        match &outcome {
+            PlaceOutcome::Occupied(_) => {
+                self.game.flash = Some(Flash {
+                    cell: point,
+                    started: Instant::now(),
+                    duration: Flash::DURATION,
+                });
+            }
+            PlaceOutcome::Cleared(p) => {
+                self.game.clearing = Some(Clearing {
+                    cells: p.cleared.clone(),
+                    started: Instant::now(),
+                });
+                self.game.flash = None;
+            }
+            PlaceOutcome::Refused => {}
+        }
+        outcome
+    }
 
-impl<R> RenderedGame<R> {
-    fn stone_label(&self, stone: game::Stone) -> &'static str {
-        self.stone_labels[stone as usize]
+    // ~ appends a move to the recording, if any; gives up recording
+    // on failure
+    fn record(&mut self, step: Step) {
+        if let Some(moves) = &mut self.moves {
+            moves.push(step);
+        }
+        if let Some(Err(e)) = self.recorder.as_mut().map(|r| r.record(step)) {
+            self.recorder = None;
+            self.notice = Some(Cow::Owned(format!("Failed to record the game: {e}")));
+        }
     }
 
-    fn stone_style(&self, stone: game::Stone) -> Style {
-        self.stone_styles[stone as usize]
+    // ~ describes the current game for a recording
+    fn replay_header(&self) -> replay::Header {
+        replay::Header {
+            seed: self.seed,
+            rows: self.game.state.rows(),
+            cols: self.game.state.cols(),
+            difficulty: self.difficulty,
+            preview: self.game.state.num_nexts(),
+            base: self.game.state.base(),
+            neighbours: self.game.state.neighbour_mode(),
+            start_score: self.start_score,
+            bag: self.bag,
+            random_cursor: self.random_cursor,
+            zen: self.zen,
+            goal_clears: self.goal_clears,
+        }
     }
-}
 
-impl<R> Widget for &RenderedGame<R> {
-    fn render(self, area: Rect, buf: &mut Buffer)
-    where
-        Self: Sized,
-    {
-        let area = area.intersection(buf.area);
-        if area.is_empty() {
+    // ~ applies the next move of the game being replayed
+    fn replay_step(&mut self) {
+        let Some(replay) = &mut self.replay else {
             return;
+        };
+        match replay.next_step() {
+            Some(Step::Place(point)) => {
+                if self.game.state.cell(point).is_some() {
+                    self.notice = Some(Cow::Borrowed("Replay diverged; the cell is occupied."));
+                    return;
+                }
+                if self.place_at(point) == PlaceOutcome::Refused {
+                    self.notice =
+                        Some(Cow::Borrowed("Replay diverged; the placement was refused."));
+                    return;
+                }
+            }
+            Some(Step::Undo) => {
+                self.game.state.undo();
+            }
+            Some(Step::Redo) => {
+                self.game.state.redo();
+            }
+            None => {
+                self.notice = Some(Cow::Borrowed("End of replay."));
+                return;
+            }
+        }
+        self.point = self.replay.as_ref().and_then(next_placement);
+        // ~ a replayed game does not count towards the statistics
+        if self.game.state.is_finished().is_some() {
+            self.mode = ScreenMode::GameOver;
         }
+    }
 
-        // board ------------------------------------------------------
+    // ~ switches to the screen of `mode`, e.g. the help, returning to
+    // the current one once it's left
+    fn open_screen(&mut self, mode: ScreenMode) {
+        self.return_mode = self.mode;
+        self.mode = mode;
+    }
 
-        let mut y = area.y + 1; // ~ one for the border
-        for r in 0..self.state.rows() {
-            let mut x = area.left() + 1; // ~ one for the border
-            for c in 0..self.state.cols() {
-                if let Some(s) = self.state.get(r, c) {
-                    buf[Position { x, y }]
-                        .set_style(self.stone_style(s).bold())
-                        .set_symbol(self.stone_label(s));
-                }
-                x += 2;
-            }
-            y += 1;
+    // ~ asks the question unless confirmations are turned off, in
+    // which case it acts on it right away
+    fn ask(&mut self, question: Question) {
+        if self.confirm {
+            self.mode = ScreenMode::Confirm(question);
+        } else {
+            self.answer(question);
         }
+    }
 
-        let xp = if self.packed_ui { 0 } else { 1 };
+    // ~ acts on a confirmed question
+    fn answer(&mut self, question: Question) {
+        match question {
+            Question::NewGame | Question::Restart => self.new_game(),
+            Question::Quit => self.mode = ScreenMode::Exit,
+        }
+    }
 
-        // ~ the last colum is only one char wide (in packed mode)
-        Block::bordered().border_style(Color::Reset).render(
-            Rect {
-                x: area.x,
+    // ~ abandons the current game for a new one
+    fn new_game(&mut self) {
+        let seed = self.game.state.rng().random();
+        self.new_game_with_seed(seed);
+    }
+
+    // ~ abandons the current game for the one determined by `seed`
+    fn new_game_with_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.resumed = false;
+        self.daily = None;
+        self.reset_game();
+        self.timer = Timer::default();
+        self.mode = ScreenMode::Playing;
+        self.replay = None;
+        self.moves = Some(Vec::new());
+        self.playback = None;
+        let header = self.replay_header();
+        if let Some(Err(e)) = self.recorder.as_mut().map(|r| r.restart(&header)) {
+            self.recorder = None;
+            self.notice = Some(Cow::Owned(format!("Failed to record the game: {e}")));
+        }
+    }
+
+    // ~ sets up the game determined by the current seed afresh
+    fn reset_game(&mut self) {
+        self.game
+            .state
+            .reinit_with_rng(self.difficulty, (self.new_rng)(self.seed));
+        self.game.state.set_start_score(self.start_score);
+        fill_bag(&mut self.game.state, self.bag);
+        self.game.marker = None;
+        self.misses = 0;
+        self.point = if self.random_cursor {
+            Some(self.game.state.initial_cursor())
+        } else {
+            self.game.state.find_free_any(Cursor::default())
+        };
+    }
+
+    // ~ plays the moves of the game just won back on their own from
+    // the start; ending up in the very same state
+    fn play_back(&mut self) {
+        let Some(moves) = self.moves.take() else {
+            return;
+        };
+        self.replay = Some(Replay::new(self.replay_header(), moves));
+        self.reset_game();
+        self.game.clearing = None;
+        self.game.flash = None;
+        self.mode = ScreenMode::Replay;
+        self.playback = Some(Instant::now());
+    }
+
+    // ~ makes the next move of the game playing itself: the clearing
+    // placement nearest to the cursor, else the one of the solver;
+    // starts a new game once the current one is over, which (played
+    // by the demo) doesn't count towards the statistics
+    fn demo_step(&mut self) {
+        if !matches!(self.mode, ScreenMode::Playing) {
+            return;
+        }
+        let game = &self.game.state;
+        let point = self
+            .point
+            .and_then(|p| game.find_nearest_clearing(p))
+            .or_else(|| solver::next_move(game));
+        let Some(point) = point.filter(|_| game.is_finished().is_none()) else {
+            self.new_game();
+            return;
+        };
+        self.point = Some(point);
+        match self.place_at(point) {
+            PlaceOutcome::Refused => {
+                self.new_game();
+                return;
+            }
+            PlaceOutcome::Occupied(_) => self.point = self.game.state.find_free_any(point),
+            PlaceOutcome::Cleared(_) => {}
+        }
+        self.record(Step::Place(point));
+    }
+
+    // ~ hands the game over from the demo to the player; a fresh one if
+    // the demo's is over already
+    fn stop_demo(&mut self) {
+        self.demo = None;
+        if self.game.state.is_finished().is_some() {
+            self.new_game();
+        }
+    }
+
+    // ~ true if there are steps left to replay
+    fn replay_progress(&self) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|r| r.peek_step().is_some())
+    }
+
+    // ~ switches to the game-over screen once the current game came
+    // to its end and accounts for the game in the statistics
+    fn game_over(&mut self) {
+        self.mode = ScreenMode::GameOver;
+        if let Some(finished) = self.game.state.is_finished() {
+            self.stats.record(
+                &finished,
+                self.game.state.num_placed(),
+                self.timer.elapsed(),
+            );
+            if let (Some(date), game::Finished::Success) = (self.daily, &finished) {
+                self.stats
+                    .record_daily(date.seed(), self.game.state.num_placed());
+            }
+            if let Err(e) = self.stats.save() {
+                self.notice = Some(Cow::Owned(format!("Failed to save statistics: {e:#}")));
+            }
+            if matches!(finished, game::Finished::Success) {
+                self.play_back();
+            }
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = paths::save_file().context("no location for saved games")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut w = io::BufWriter::new(std::fs::File::create(&path)?);
+        self.game.state.save_to_writer(&mut w)?;
+        io::Write::flush(&mut w)?;
+        Ok(())
+    }
+
+    fn move_cursor(&mut self, direction: game::Direction) {
+        if let Some(point) = self.point {
+            self.point = self.game.state.find_free_next(point, direction);
+        }
+    }
+}
+
+// ~ a bar chart of how often each stone in play was placed in the
+// game so far, and how often it cleared any stones; one column per
+// stone
+fn stone_histogram<R>(game: &Game<R>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let stats = game.stats();
+    let max = stats.placed_per_stone.iter().copied().max().unwrap_or(0);
+    let bars = |counts: &[usize]| -> String {
+        counts[..game.base()]
+            .iter()
+            .map(|&n| match n {
+                0 => ' ',
+                n => BARS[(n * BARS.len()).div_ceil(max) - 1],
+            })
+            .collect()
+    };
+    let digits: String = (0..game.base())
+        .map(|d| char::from(b'0' + d as u8))
+        .collect();
+    format!(
+        "placed   {}\ncleared  {}\n         {digits}",
+        bars(&stats.placed_per_stone),
+        bars(&stats.clearing_per_stone),
+    )
+}
+
+// ~ resets the colors of all cells of `buf`; leaving just the text
+// and its modifiers (e.g. bold or reversed) for monochrome terminals
+fn strip_colors(buf: &mut Buffer) {
+    for cell in &mut buf.content {
+        cell.set_fg(Color::Reset).set_bg(Color::Reset);
+    }
+}
+
+// ~ the cell the next step of the replay places a stone onto; if any
+fn next_placement(replay: &Replay) -> Option<Cursor> {
+    match replay.peek_step() {
+        Some(Step::Place(point)) => Some(point),
+        _ => None,
+    }
+}
+
+// ~ has `game` played with a finite bag of `n` of each stone in play
+// unless `n` is zero
+fn fill_bag<R: Rng>(game: &mut Game<R>, n: usize) {
+    if n > 0 {
+        game.set_finite_bag(&vec![n; game.base()])
+            .expect("one count per stone in play");
+    }
+}
+
+// ~ a line telling what placing the next stone at `marker` would do
+fn marker_outcome<R>(game: &Game<R>, marker: Cursor) -> Line<'static> {
+    match game.would_clear(marker).count() {
+        0 => Line::raw("+ occupies"),
+        n => Line::raw(format!("+ clears {n}")),
+    }
+    .fg(Color::Yellow)
+}
+
+// ~ a line telling the last digit of the neighbours' sum the next
+// stone needs for clearing them (i.e. the stone itself) and the one
+// the neighbours of `point` have
+fn need_have<R>(game: &Game<R>, point: Cursor) -> Option<Line<'static>> {
+    let next = game.nexts().next()?;
+    let line = match game.neighbour_sum(point) {
+        (0, _) => format!("need …{next}, have none"),
+        (_, sum) => format!("need …{next}, have …{sum}"),
+    };
+    Some(Line::raw(line).cyan())
+}
+
+// ~ a line telling how far the placements so far are over or under
+// the game's par; green while at or under par, red once over it
+fn par_indicator<R>(game: &Game<R>) -> Line<'static> {
+    let (placed, par) = (game.num_placed(), game.par());
+    let line = match placed.cmp(&par) {
+        std::cmp::Ordering::Less => Line::raw(format!("{} under par", par - placed)).green(),
+        std::cmp::Ordering::Equal => Line::raw("on par").green(),
+        std::cmp::Ordering::Greater => Line::raw(format!("+{} over par", placed - par)).red(),
+    };
+    line.right_aligned()
+}
+
+// ~ the offset of a view of length `view` onto something of length
+// `total` scrolled minimally from `offset` such that `lo..hi` is in view
+fn scroll_to(offset: u16, lo: u16, hi: u16, view: u16, total: u16) -> u16 {
+    let offset = if lo < offset {
+        lo
+    } else if hi > offset + view {
+        hi - view
+    } else {
+        offset
+    };
+    offset.min(total.saturating_sub(view))
+}
+
+// ~ the number of lines `text` takes when wrapped at word boundaries
+// to `width` columns, as by `Wrap { trim: true }`; words wider than
+// that are broken up
+fn wrapped_line_count(text: &str, width: u16) -> usize {
+    let width = usize::from(width.max(1));
+    let mut num_lines = 0;
+    for line in text.lines() {
+        num_lines += 1;
+        let mut used = 0;
+        // ~ the spaces between the previous word and the next one;
+        // kept in full unless wrapping in between
+        let mut gap = 0;
+        for word in line.split(' ') {
+            if word.is_empty() {
+                gap += 1;
+                continue;
+            }
+            let w = Span::raw(word).width();
+            let gap = mem::replace(&mut gap, 1);
+            if used > 0 && used + gap + w <= width {
+                used += gap + w;
+                continue;
+            }
+            if used > 0 {
+                num_lines += 1;
+            }
+            num_lines += (w - 1) / width;
+            used = w - (w - 1) / width * width;
+        }
+    }
+    num_lines
+}
+
+// ~ the hint for the (first) key bound to `action` along with `label`
+// telling what it does; the key is picked out from the label if the
+// label starts with it, e.g. "q" in "quit".  Nothing if the action
+// isn't bound to any key
+fn key_hint(
+    keymap: &Keymap,
+    action: Action,
+    label: &str,
+    style: Style,
+) -> Option<Vec<Span<'static>>> {
+    let key = keymap.keys_for(action).first()?.label();
+    let style = style.fg(Color::Magenta);
+    Some(match label.strip_prefix(key.as_str()) {
+        Some(rest) if !key.starts_with('<') => {
+            vec![Span::styled(key, style), Span::raw(rest.to_owned())]
+        }
+        _ => vec![Span::styled(key, style), Span::raw(format!(" {label}"))],
+    })
+}
+
+// ~ the hint line made up of the given hints separated from each
+// other; skipping the missing ones
+fn hint_line(hints: impl IntoIterator<Item = Option<Vec<Span<'static>>>>) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for hint in hints.into_iter().flatten() {
+        if spans.len() > 1 {
+            spans.push(Span::raw(" | "));
+        }
+        spans.extend(hint);
+    }
+    Line::from(spans)
+}
+
+// ~ renders a `TextScreen` above the status line; tells the number of
+// lines of text shown at once, i.e. the page to scroll by
+fn render_text_screen(frame: &mut Frame, title: &str, text: &str, scroll: &mut u16) -> u16 {
+    let area = Rect {
+        height: frame.area().height.saturating_sub(1),
+        ..frame.area()
+    };
+    frame.render_stateful_widget(TextScreen { title, text }, area, scroll);
+    area.height.saturating_sub(2)
+}
+
+// ~ renders a message across the middle of the screen
+fn render_message(frame: &mut Frame, s: &str) {
+    let frame_area = frame.area();
+    // ~ make the row above and below blank as well; unless there's no
+    // room for them.  a message taller than the frame covers it all,
+    // showing its start.
+    let lines = s.lines().count() as u16;
+    let pad = u16::from(lines + 2 <= frame_area.height);
+    let height = (lines + 2 * pad).min(frame_area.height);
+    let area = Rect {
+        x: frame_area.x,
+        y: frame_area.y
+            + (frame_area.height / 2)
+                .saturating_sub(height / 2 + 1)
+                .min(frame_area.height - height),
+        width: frame_area.width,
+        height,
+    };
+    frame.render_widget(Clear, area);
+    // ~ shrink the area
+    let area = Rect {
+        y: area.y + pad,
+        height: area.height - 2 * pad,
+        ..area
+    };
+    frame.render_widget(Paragraph::new(s).centered(), area);
+}
+
+// --------------------------------------------------------------------
+
+/// A stopwatch which can be paused and resumed
+#[derive(Default)]
+struct Timer {
+    // ~ the time accumulated by previous runs
+    elapsed: Duration,
+    // ~ the start of the current run; `None` while paused
+    running_since: Option<Instant>,
+}
+
+impl Timer {
+    fn set_running(&mut self, running: bool) {
+        match (running, self.running_since) {
+            (true, None) => self.running_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.elapsed += since.elapsed();
+                self.running_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.elapsed + self.running_since.map(|t| t.elapsed()).unwrap_or_default()
+    }
+}
+
+// --------------------------------------------------------------------
+
+struct RenderedGame<R> {
+    state: Game<R>,
+    stone_labels: [&'static str; game::NUM_STONES],
+    stone_styles: [Style; game::NUM_STONES],
+    // ~ true to "pack / cram / squeeze" the UI a bit; used in
+    // non-wide mode to cut back on non-elegant visual "gaps"
+    packed_ui: bool,
+    // ~ true to pack the board even further: one column per cell
+    // rather than two; implies `packed_ui`
+    mini: bool,
+    // ~ the cursor position to highlight and to visualize the effect
+    // of a placement for; `None` if there's nothing to visualize
+    cursor: Option<Cursor>,
+    // ~ the stones being cleared right now, if any
+    clearing: Option<Clearing>,
+    // ~ the cell just occupied by a placement, if any
+    flash: Option<Flash>,
+    // ~ true to show the neighbours' sum on the free cells next to
+    // a stone
+    show_sums: bool,
+    // ~ true to mark the free cells which have been occupied before
+    // by a faint dot
+    show_trail: bool,
+    // ~ a free cell to visualize the effect of a placement for apart
+    // from the cursor; `None` if not set
+    marker: Option<Cursor>,
+    // ~ true to label the columns (by letters) and rows (by numbers)
+    // of the board
+    coords: bool,
+    // ~ false to draw without any colors; see `strip_colors`
+    color: bool,
+}
+
+// ~ how long to wait for input at most while nothing changes on the
+// screen on its own
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ~ how long the outcome of a placement is told on the hint line
+const STATUS_DURATION: Duration = Duration::from_millis(1500);
+
+// ~ the pace of the game playing itself in the demo mode
+const DEMO_STEP: Duration = Duration::from_millis(500);
+
+// ~ the pace of playing back a won game
+const PLAYBACK_STEP: Duration = Duration::from_millis(300);
+
+/// An animation of stones fading away from the board
+struct Clearing {
+    // ~ the cleared cells along with the stones which were on them
+    cells: Vec<(Cursor, game::Stone)>,
+    started: Instant,
+}
+
+impl Clearing {
+    const FRAME: Duration = Duration::from_millis(80);
+    const NUM_FRAMES: u32 = 3;
+
+    // ~ the current frame of the animation
+    fn frame(&self) -> u32 {
+        (self.started.elapsed().as_millis() / Self::FRAME.as_millis()) as u32
+    }
+
+    fn is_done(&self) -> bool {
+        self.frame() >= Self::NUM_FRAMES
+    }
+}
+
+/// A brief flash of a cell occupied by a placement which didn't clear
+/// anything; or a longer one pointing out a clearing place (see
+/// `--assist`)
+struct Flash {
+    cell: Cursor,
+    started: Instant,
+    duration: Duration,
+}
+
+impl Flash {
+    const DURATION: Duration = Duration::from_millis(120);
+    const ASSIST_DURATION: Duration = Duration::from_millis(1500);
+
+    fn is_done(&self) -> bool {
+        self.started.elapsed() >= self.duration
+    }
+
+    fn is_assist(&self) -> bool {
+        self.duration == Self::ASSIST_DURATION
+    }
+}
+
+// ~ the number of placements in a row which don't clear anything
+// before pointing out a clearing place (see `App::assist`)
+const ASSIST_AFTER: usize = 3;
+
+// ~ the labels of the stones in the packed and the wide UI
+const PACKED_LABELS: [&str; game::NUM_STONES] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+const WIDE_LABELS: [&str; game::NUM_STONES] =
+    ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"];
+
+impl<R> RenderedGame<R> {
+    // ~ switches between drawing with full-width characters and the
+    // packed UI
+    fn set_wide(&mut self, wide: bool) {
+        self.packed_ui = !wide;
+        self.mini &= !wide;
+        self.stone_labels = if wide { WIDE_LABELS } else { PACKED_LABELS };
+    }
+
+    // ~ the number of rows needed by the "nexts" area: the stones
+    // with separators in between, a closing line, and the count of
+    // placements
+    fn nexts_rows(&self) -> usize {
+        self.shown_nexts() * 2 + 1
+    }
+
+    // ~ the number of previewed stones shown; as many as fit next to
+    // the board (but at least one), see `render`
+    fn shown_nexts(&self) -> usize {
+        self.state
+            .num_nexts()
+            .min((self.state.rows() - 1) / 2)
+            .max(1)
+    }
+
+    /// Determines the (width, height) needed to render the game.
+    fn size(&self) -> (u16, u16) {
+        let (ox, oy) = self.board_offset();
+        let height = self.state.rows().max(self.nexts_rows()) as u16 + 2;
+        let width = 2 /* borders (left, right) */ + self.board_width() + self.nexts_width();
+        (ox + width, oy + height)
+    }
+
+    // ~ the number of columns between a cell and the next one in a row
+    fn cell_step(&self) -> u16 {
+        if self.mini { 1 } else { 2 }
+    }
+
+    // ~ the width of the board within its borders; the last column is
+    // only one char wide in the packed UI
+    fn board_width(&self) -> u16 {
+        let width = self.state.cols() as u16 * self.cell_step();
+        if self.packed_ui && !self.mini {
+            width - 1
+        } else {
+            width
+        }
+    }
+
+    // ~ the width of the "nexts" area including its borders
+    fn nexts_width(&self) -> u16 {
+        if self.packed_ui { 5 } else { 6 }
+    }
+
+    // ~ the (x, y) offset of the board's border from the top left of
+    // the rendered game; leaving room for the coordinates, if shown
+    fn board_offset(&self) -> (u16, u16) {
+        if self.coords {
+            (self.state.rows().to_string().len() as u16, 1)
+        } else {
+            (0, 0)
+        }
+    }
+
+    fn stone_label(&self, stone: game::Stone) -> &'static str {
+        self.stone_labels[stone as usize]
+    }
+
+    // ~ the style of the given stone; a plain one when drawing
+    // without colors
+    fn stone_style(&self, stone: game::Stone) -> Style {
+        if self.color {
+            self.stone_styles[stone as usize]
+        } else {
+            Style::new()
+        }
+    }
+}
+
+impl<R> Widget for &RenderedGame<R> {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        // ~ column letters (wrapping around after 'z') across the top
+        // and row numbers down the left of the board
+        let (ox, oy) = self.board_offset();
+        if self.coords {
+            let style = Style::new().fg(Color::DarkGray);
+            for c in 0..self.state.cols() {
+                let letter = char::from(b'a' + (c % 26) as u8);
+                buf[Position {
+                    x: area.x + ox + 1 + c as u16 * self.cell_step(),
+                    y: area.y,
+                }]
+                .set_style(style)
+                .set_char(letter);
+            }
+            for r in 0..self.state.rows() {
+                Line::styled((r + 1).to_string(), style)
+                    .right_aligned()
+                    .render(
+                        Rect {
+                            x: area.x,
+                            y: area.y + oy + 1 + r as u16,
+                            width: ox,
+                            height: 1,
+                        },
+                        buf,
+                    );
+            }
+        }
+        let area = Rect {
+            x: area.x + ox,
+            y: area.y + oy,
+            width: area.width.saturating_sub(ox),
+            height: area.height.saturating_sub(oy),
+        };
+
+        // board ------------------------------------------------------
+
+        let mut y = area.y + 1; // ~ one for the border
+        for r in 0..self.state.rows() {
+            let mut x = area.left() + 1; // ~ one for the border
+            for c in 0..self.state.cols() {
+                if let Some(s) = self.state.get(r, c) {
+                    buf[Position { x, y }]
+                        .set_style(self.stone_style(s).bold())
+                        .set_symbol(self.stone_label(s));
+                } else if self.show_trail
+                    && self.state.was_occupied(Cursor {
+                        x: c as u8,
+                        y: r as u8,
+                    })
+                {
+                    buf[Position { x, y }]
+                        .set_style(Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM))
+                        .set_symbol("·");
+                }
+                x += self.cell_step();
+            }
+            y += 1;
+        }
+
+        // ~ the last digit of the neighbours' sum on free cells which
+        // have any neighbours
+        if self.show_sums {
+            for p in self.state.free_cells() {
+                let (cnt, sum) = self.state.neighbour_sum(p);
+                if cnt > 0
+                    && let Ok(sum) = game::Stone::try_from(sum as u8)
+                {
+                    buf[Position {
+                        x: area.x + 1 + p.x as u16 * self.cell_step(),
+                        y: area.y + 1 + p.y as u16,
+                    }]
+                    .set_style(Style::new().fg(Color::DarkGray).add_modifier(Modifier::DIM))
+                    .set_symbol(self.stone_label(sum));
+                }
+            }
+        }
+
+        // ~ fade out the stones cleared just recently
+        if let Some(clearing) = &self.clearing {
+            let fade = match clearing.frame() {
+                0 => Modifier::BOLD,
+                1 => Modifier::empty(),
+                _ => Modifier::DIM,
+            };
+            for &(p, s) in &clearing.cells {
+                if self.state.cell(p).is_none() {
+                    buf[Position {
+                        x: area.x + 1 + p.x as u16 * self.cell_step(),
+                        y: area.y + 1 + p.y as u16,
+                    }]
+                    .set_style(self.stone_style(s).add_modifier(fade))
+                    .set_symbol(self.stone_label(s));
+                }
+            }
+        }
+
+        if let Some(flash) = &self.flash {
+            buf[Position {
+                x: area.x + 1 + flash.cell.x as u16 * self.cell_step(),
+                y: area.y + 1 + flash.cell.y as u16,
+            }]
+            .modifier
+            .insert(Modifier::REVERSED);
+        }
+
+        // ~ highlight the stones a placement at the cursor would clear
+        if let Some(cursor) = self.cursor {
+            for p in self.state.would_clear(cursor) {
+                buf[Position {
+                    x: area.x + 1 + p.x as u16 * self.cell_step(),
+                    y: area.y + 1 + p.y as u16,
+                }]
+                .modifier
+                .insert(Modifier::REVERSED);
+            }
+            // ~ mark the cell at the cursor itself; the terminal's
+            // caret alone is easily missed on some terminals
+            let y = area.y + 1 + cursor.y as u16;
+            let x = area.x + 1 + cursor.x as u16 * self.cell_step();
+            let width = if self.packed_ui { 1 } else { 2 };
+            for x in x..x + width {
+                if self.color {
+                    buf[Position { x, y }].set_bg(Color::DarkGray);
+                } else {
+                    buf[Position { x, y }].modifier.insert(Modifier::REVERSED);
+                }
+            }
+        }
+
+        // ~ mark the stones a placement at the marker would clear, and
+        // the marker itself
+        if let Some(marker) = self.marker {
+            for p in self.state.would_clear(marker) {
+                buf[Position {
+                    x: area.x + 1 + p.x as u16 * self.cell_step(),
+                    y: area.y + 1 + p.y as u16,
+                }]
+                .modifier
+                .insert(Modifier::UNDERLINED);
+            }
+            buf[Position {
+                x: area.x + 1 + marker.x as u16 * self.cell_step(),
+                y: area.y + 1 + marker.y as u16,
+            }]
+            .set_symbol(if self.packed_ui { "+" } else { "＋" })
+            .set_fg(Color::Yellow);
+        }
+
+        Block::bordered().border_style(Color::Reset).render(
+            Rect {
+                x: area.x,
                 y: area.y,
-                width: 1 + xp + self.state.cols() as u16 * 2,
+                width: 2 + self.board_width(),
                 height: self.state.rows() as u16 + 2,
             },
             buf,
@@ -394,63 +2091,88 @@ impl<R> Widget for &RenderedGame<R> {
 
         Block::bordered().border_style(Color::Reset).render(
             Rect {
-                x: area.x + 1 + xp + self.state.cols() as u16 * 2,
+                x: area.x + 2 + self.board_width(),
                 y: area.y,
-                width: if self.packed_ui { 5 } else { 6 },
-                height: self.state.rows() as u16 + 2,
+                width: self.nexts_width(),
+                height: self.state.rows().max(self.nexts_rows()) as u16 + 2,
             },
             buf,
         );
-        let x = area.x + 1 + xp + self.state.cols() as u16 * 2 + 1 + 1;
+        let x = area.x + 2 + self.board_width() + 2;
         if area.y > 0 {
+            // ~ over the nexts rather than right after the labels of the
+            // board's columns, if shown
             buf[Position {
-                x: x - 3,
+                x: if self.coords { x - 2 } else { x - 3 },
                 y: area.y - 1,
             }]
             .set_symbol("↶")
             .set_fg(Color::DarkGray);
         }
         y = area.y + 1;
-        for (i, s) in self.state.nexts().enumerate() {
+        let nexts = self.state.nexts();
+        let num_nexts = nexts.len();
+        let shown = self.shown_nexts().min(num_nexts);
+        for (i, s) in nexts.take(shown).enumerate() {
             if i > 0 {
                 buf[Position { x, y }]
                     .set_symbol(if self.packed_ui { "↑" } else { " ￪" })
                     .set_fg(Color::DarkGray);
                 y += 1;
             }
+            // ~ point at the stone to place next; dim the ones after it
             let mut style = self.stone_style(s);
             if i == 0 {
                 style = style.bold();
+                buf[Position { x: x - 1, y }].set_symbol("▶");
+            } else {
+                style = style.dim();
             }
             buf[Position { x, y }]
                 .set_style(style)
                 .set_symbol(self.stone_label(s));
             y += 1;
         }
-        buf[Position { x, y }]
-            .set_symbol(if self.packed_ui { "—" } else { "——" })
-            .set_fg(Color::DarkGray);
+        // ~ the closing line tells the number of stones not shown, if
+        // any
+        match num_nexts - shown {
+            0 => {
+                buf[Position { x, y }]
+                    .set_symbol(if self.packed_ui { "—" } else { "——" })
+                    .set_fg(Color::DarkGray);
+            }
+            hidden => {
+                Line::raw(format!("+{hidden}")).fg(Color::DarkGray).render(
+                    Rect {
+                        x,
+                        y,
+                        width: 2,
+                        height: 1,
+                    },
+                    buf,
+                );
+            }
+        }
         y += 1;
 
         // num_placed stones so far -----------------------------------
 
         {
+            // ~ right aligned within the inner width of the "nexts"
+            // panel; abbreviated if it doesn't fit
+            let width = if self.packed_ui { 3 } else { 4 };
             let mut b = itoa::Buffer::new();
             let s = b.format(self.state.num_placed());
-            let s_len = s.len();
-            Span::raw(s).render(
+            let s = if s.len() > width {
+                Cow::Owned("9".repeat(width - 1) + "+")
+            } else {
+                Cow::Borrowed(s)
+            };
+            Line::raw(s).right_aligned().render(
                 Rect {
-                    x: if self.packed_ui {
-                        if s.len() > 1 { x - 1 } else { x }
-                    } else if s.len() < 2 {
-                        x + 1
-                    } else if s.len() < 3 {
-                        x
-                    } else {
-                        x - 1
-                    },
+                    x: x - 1,
                     y,
-                    width: s_len as u16,
+                    width: width as u16,
                     height: 1,
                 },
                 buf,
@@ -459,27 +2181,70 @@ impl<R> Widget for &RenderedGame<R> {
     }
 }
 
-struct Help;
+/// A scrollable screen of text, e.g. the help; centered horizontally
+/// and wrapped to the width of the screen (but at most
+/// `TextScreen::MAX_WIDTH` columns)
+struct TextScreen<'a> {
+    title: &'a str,
+    text: &'a str,
+}
 
-impl StatefulWidget for Help {
+impl TextScreen<'_> {
+    // ~ the width of the screen including its borders
+    const MAX_WIDTH: u16 = 64;
+}
+
+impl StatefulWidget for TextScreen<'_> {
     type State = u16;
 
     fn render(self, area: Rect, buf: &mut Buffer, scroll: &mut Self::State) {
-        if *scroll as usize + area.height as usize - 2 > HELP_LINES {
-            *scroll = HELP_LINES.saturating_sub(area.height as usize - 2) as u16;
-        }
-        Clear.render(area, buf);
-        Paragraph::new(HELP_TEXT)
+        let width = area.width.min(Self::MAX_WIDTH);
+        let area = Rect {
+            x: area.x + (area.width - width) / 2,
+            width,
+            ..area
+        };
+        let paragraph = Paragraph::new(self.text)
             .centered()
             .on_blue()
             .white()
-            .block(
-                Block::bordered()
-                    .title(HELP_TITLE)
-                    .title_alignment(Alignment::Center),
-            )
+            .wrap(Wrap { trim: true });
+        // ~ the number of lines after wrapping the text into the
+        // borders
+        let num_lines = wrapped_line_count(self.text, width.saturating_sub(2));
+        let view = (area.height as usize).saturating_sub(2);
+        let max_scroll = num_lines.saturating_sub(view);
+        if *scroll as usize > max_scroll {
+            *scroll = max_scroll as u16;
+        }
+        Clear.render(area, buf);
+        let mut block = Block::bordered()
+            .title(self.title)
+            .title_alignment(Alignment::Center);
+        if (*scroll as usize) < max_scroll {
+            block = block.title_bottom(Line::raw(" more ↓ ").right_aligned());
+        }
+        paragraph
+            .block(block)
             .scroll((*scroll, 0))
             .render(area, buf);
+        // ~ on the right border; only if there's anything to scroll
+        if max_scroll > 0 {
+            let mut state = ScrollbarState::new(max_scroll + 1)
+                .position(*scroll as usize)
+                .viewport_content_length(view);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .render(
+                    area.inner(Margin {
+                        vertical: 1,
+                        horizontal: 0,
+                    }),
+                    buf,
+                    &mut state,
+                );
+        }
     }
 }
 
@@ -491,55 +2256,1097 @@ const HELP_TITLE: &str = constcat::concat!(
     " "
 );
 
-const HELP_TEXT: &str = r#"
-Your goal is to iteratively clear the 9x9 board on the left
-by placing a given number onto a free place such that the sum
-of all neighbours around it (in any direction, including the
-diagonals) modulo 10 equals the placed number;
-i.e. `sum(neighbours) % 10 == number`.  In other words, the
-last (decimal) digit of the neighbours' sum must equal the
-placed number.  If the sum matches, all neighbours disappear.
-If it doesn't, the chosen place becomes occupied.
-
-Numbers are handed out from the top of the magazine on the
-right.  You can see the next four to come in their order of
-availability; this allows you to be clever and strategic
-about the numbers' placements.
-
-Apart of clearing the board, the ultimate challenge is in
-doing so with as few placements as possible.  The current
-number of placements in a game is displayed at the bottom
-of the magazine.
+// ~ one line per paragraph; wrapped to the width of the screen
+const HELP_TEXT: &str = "
+Your goal is to iteratively clear the board on the left \
+by placing a given number onto a free place such that the sum \
+of all neighbours around it (in any direction, including the \
+diagonals) modulo 10 equals the placed number; \
+i.e. `sum(neighbours) % 10 == number`.  In other words, the \
+last (decimal) digit of the neighbours' sum must equal the \
+placed number.  If the sum matches, all neighbours disappear. \
+If it doesn't, the chosen place becomes occupied.  (Playing \
+with '--base N' takes the sum modulo N instead of 10, and \
+'--neighbours orthogonal|diagonal' considers only four of \
+the neighbours.)
+
+Numbers are handed out from the top of the magazine on the \
+right.  You can see the next four (or as many as requested \
+by '--preview') to come in their order of availability; this \
+allows you to be clever and strategic about the numbers' \
+placements.  Started with '--bag N', the magazine is refilled \
+from a bag of N of each number; the numbers left in it are \
+shown above the board.  Once it runs out, the game is lost.
+
+Apart of clearing the board, the ultimate challenge is in \
+doing so with as few placements as possible.  The current \
+number of placements in a game is displayed at the bottom \
+of the magazine; below the board you see how it compares to \
+the game's par, a target derived from the number of numbers \
+initially on the board.  Above the board you see how many \
+numbers are left on it.
 
 --
 
-To move around the board  use the arrow keys.  The cursor
-will jump from one free place to the next.  Press 'space'
-to place the next, top number from the magazine to the
-current cursor position on the board.
+Move the cursor around the board with the arrow keys, or as in \
+vi.  The cursor will jump from one free place \
+to the next; wrapping around the edges of the board unless \
+started with '--no-wrap'.  Started with '--smooth-keys', a \
+held key moves the cursor at a steady pace; speeding up after \
+a second.  Then place the next, \
+top number from the magazine at the current cursor position \
+on the board.  Alternatively, click a free place to put the \
+number there right away, or right-click it to just move the \
+cursor.  A placement which doesn't clear anything flashes \
+the place and rings the bell; unless started with \
+'--no-bell'.  Started with '--assist', three such placements \
+in a row flash a place where the next number would clear its \
+neighbours instead.  Started with '--refuse-isolated', placements \
+onto places without any number around them are refused.  Started \
+with '--zen', a placement which would fill up the board \
+rather clears the number along with its neighbours; the game \
+only ends once the board is cleared.
+
+Placements can be taken back, and undone ones redone.  Stuck?  \
+Have the cursor moved to a place where the next number would \
+clear its neighbours, or to the nearest such place.  Lost \
+track of the cursor?  Bring it back to the middle of the \
+board.  \
+You can toggle showing the last digit of the \
+neighbours' sum on the free places next to a number, the \
+faint dots on the free places a number has been \
+cleared from (or start with '--no-trail'), and telling \
+the last digit the next number needs the sum \
+to end in right below the board; along with the one of the sum \
+around the cursor.  \
+A marker dropped at the cursor \
+shows what placing the next number there would do while \
+you move the cursor elsewhere.  You can switch between \
+drawing with full-width characters and the packed layout; \
+started with '--mini', the board is packed even further into \
+a single column per place, e.g. for a small overlay. \
+Started with '--coords', the columns and rows of the board \
+are labeled by letters and numbers, e.g. to talk about 'd5'. \
+A game in progress can be saved, and resumed later \
+by starting with '--resume', or abandoned \
+for a fresh board.  Your \
+statistics over all the games played so far are kept, too.  Once a game \
+is over, you can type in the seed of the next game to \
+play; e.g. to replay a game revealed by '--reveal-seed'.  A \
+won game is played back move by move right away; press any \
+key to skip to its end.
+
+Which key does all of this - as currently bound, see \
+'keys.toml' - is listed in the overview of the keys; open it \
+with the key told at the bottom of this screen.
 
 --
 
-This version of the game is a nostalgic remake of
-"Summing for PalmOS" (https://palmdb.net/app/summing-math);
+This version of the game is a nostalgic remake of \
+\"Summing for PalmOS\" (https://palmdb.net/app/summing-math); \
 written in Rust with Ratatui.
 
 --
 
 Enjoy, and have fun!
-"#;
+";
+
+// ~ reads (up to) the given number of lines of a board from `r`
+// leaving anything after them unread
+fn board_lines<B: io::BufRead>(mut r: B, rows: usize) -> io::Result<String> {
+    let mut lines = String::new();
+    for _ in 0..rows {
+        if r.read_line(&mut lines)? == 0 {
+            break;
+        }
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use rand::SeedableRng;
+    use ratatui::{
+        Terminal,
+        backend::Backend,
+        backend::TestBackend,
+        buffer::Buffer,
+        crossterm::event::{
+            Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        layout::Rect,
+        style::{Color, Modifier},
+        text::Span,
+        widgets::{Paragraph, StatefulWidget, Widget, Wrap},
+    };
+
+    use super::{
+        App, Cursor, DEMO_STEP, Flash, Game, HELP_TEXT, IDLE_TIMEOUT, Keymap, PACKED_LABELS,
+        PLAYBACK_STEP, RenderedGame, ScreenMode, TextScreen, Timer, WIDE_LABELS, board_lines,
+        exit_status, marker_outcome, need_have, scroll_to, stone_histogram, strip_colors,
+        wrapped_line_count,
+    };
+    use crate::{
+        game::{Difficulty, Finished},
+        replay::Step,
+        rng::Pcg32,
+        stats::Stats,
+        theme::Palette,
+    };
+
+    // ~ a game restored from the given board (rows of digits and
+    // dots), the stones previewed, and the placements so far
+    fn rendered_game(
+        board: &[&str],
+        nexts: &str,
+        num_placed: usize,
+        wide: bool,
+    ) -> RenderedGame<Pcg32> {
+        let saved = format!(
+            "summing-save 1\n{} {}\n{num_placed}\n{nexts}\n{}\n",
+            board.len(),
+            board[0].len(),
+            board.join("\n")
+        );
+        RenderedGame {
+            state: Game::load_full_from_reader(saved.as_bytes(), Pcg32::seed_from_u64(0)).unwrap(),
+            stone_labels: if wide { WIDE_LABELS } else { PACKED_LABELS },
+            stone_styles: Palette::Default.stone_styles(),
+            packed_ui: !wide,
+            mini: false,
+            cursor: None,
+            clearing: None,
+            flash: None,
+            show_sums: false,
+            show_trail: true,
+            marker: None,
+            coords: false,
+            color: true,
+        }
+    }
+
+    // ~ renders the game onto a terminal of just the needed size
+    fn render(game: &RenderedGame<Pcg32>) -> Vec<String> {
+        let (width, height) = game.size();
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(game, frame.area()))
+            .unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    // ~ the text of the buffer's lines as seen on a terminal; ignoring
+    // any styles
+    fn buffer_lines(buf: &Buffer) -> Vec<String> {
+        buf.content
+            .chunks(buf.area.width as usize)
+            .map(|cells| {
+                let mut line = String::new();
+                // ~ cells covered by a preceding wide symbol
+                let mut skip = 0;
+                for c in cells {
+                    if skip == 0 {
+                        line.push_str(c.symbol());
+                    }
+                    skip = skip.max(Span::raw(c.symbol()).width()).saturating_sub(1);
+                }
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_num_placed() {
+        let board = ["...", ".1.", "..."];
+        for (num_placed, label) in [(7, "  7"), (42, " 42"), (999, "999"), (1000, "99+")] {
+            let count = format!("│     ││{label}│");
+            assert_eq!(
+                vec![
+                    "┌─────┐┌───┐",
+                    "│     ││▶5 │",
+                    "│  1  ││ — │",
+                    count.as_str(),
+                    "└─────┘└───┘",
+                ],
+                render(&rendered_game(&board, "5", num_placed, false))
+            );
+        }
+        for (num_placed, label) in [(7, "   7"), (9999, "9999"), (10000, "999+")] {
+            let count = format!("│      ││{label}│");
+            assert_eq!(
+                vec![
+                    "┌──────┐┌────┐",
+                    "│      ││▶５ │",
+                    "│  １  ││ —— │",
+                    count.as_str(),
+                    "└──────┘└────┘",
+                ],
+                render(&rendered_game(&board, "5", num_placed, true))
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_snapshots() {
+        let empty = [".....", ".....", ".....", ".....", "....."];
+        let full = ["01234", "56789", "01234", "56789", "01234"];
+        assert_eq!(
+            vec![
+                "┌─────────┐┌───┐",
+                "│         ││▶1 │",
+                "│         ││ ↑ │",
+                "│         ││ 2 │",
+                "│         ││ — │",
+                "│         ││  3│",
+                "└─────────┘└───┘",
+            ],
+            render(&rendered_game(&empty, "12", 3, false))
+        );
+        assert_eq!(
+            vec![
+                "┌──────────┐┌────┐",
+                "│          ││▶１ │",
+                "│          ││  ￪ │",
+                "│          ││ ２ │",
+                "│          ││ —— │",
+                "│          ││   3│",
+                "└──────────┘└────┘",
+            ],
+            render(&rendered_game(&empty, "12", 3, true))
+        );
+        assert_eq!(
+            vec![
+                "┌─────────┐┌───┐",
+                "│0 1 2 3 4││▶1 │",
+                "│5 6 7 8 9││ ↑ │",
+                "│0 1 2 3 4││ 2 │",
+                "│5 6 7 8 9││ — │",
+                "│0 1 2 3 4││  3│",
+                "└─────────┘└───┘",
+            ],
+            render(&rendered_game(&full, "12", 3, false))
+        );
+        assert_eq!(
+            vec![
+                "┌──────────┐┌────┐",
+                "│０１２３４││▶１ │",
+                "│５６７８９││  ￪ │",
+                "│０１２３４││ ２ │",
+                "│５６７８９││ —— │",
+                "│０１２３４││   3│",
+                "└──────────┘└────┘",
+            ],
+            render(&rendered_game(&full, "12", 3, true))
+        );
+    }
+
+    #[test]
+    fn test_size() {
+        // ~ the board's borders, its cells (the last one just a char
+        // wide in the packed layout), and the nexts with their borders
+        let board = [".....", ".1...", ".....", ".....", "....."];
+        for (wide, size) in [(false, (16, 7)), (true, (18, 7))] {
+            let game = rendered_game(&board, "12", 0, wide);
+            assert_eq!(size, game.size(), "wide: {wide}");
+            // ~ the nexts panel ends right at the edge
+            let (w, h) = game.size();
+            let mut buf = Buffer::empty(Rect::new(0, 0, w, h));
+            (&game).render(buf.area, &mut buf);
+            assert_eq!("┐", buf[(w - 1, 0)].symbol(), "wide: {wide}");
+            assert_eq!("┘", buf[(w - 1, h - 1)].symbol(), "wide: {wide}");
+        }
+    }
+
+    #[test]
+    fn test_render_nexts() {
+        let board = ["...", ".1.", "...", "...", "...", "...", "..."];
+        for wide in [false, true] {
+            let game = rendered_game(&board, "123", 0, wide);
+            let (w, h) = game.size();
+            let mut buf = Buffer::empty(Rect::new(0, 0, w, h));
+            (&game).render(buf.area, &mut buf);
+            // ~ the column of the marker and the stones right after it
+            let x = w - if wide { 5 } else { 4 };
+            let column: Vec<_> = (1..h - 1).map(|y| &buf[(x, y)]).collect();
+            assert_eq!("▶", column[0].symbol(), "wide: {wide}");
+            assert!(
+                column[1..].iter().all(|c| c.symbol() == " "),
+                "wide: {wide}"
+            );
+            let stones: Vec<_> = (1..6).step_by(2).map(|y| &buf[(x + 1, y)]).collect();
+            assert!(stones[0].modifier.contains(Modifier::BOLD));
+            assert!(!stones[0].modifier.contains(Modifier::DIM));
+            assert!(
+                stones[1..]
+                    .iter()
+                    .all(|c| c.modifier.contains(Modifier::DIM))
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_coords() {
+        let mut game = rendered_game(&["...", ".1.", "..."], "5", 0, false);
+        game.coords = true;
+        assert_eq!(
+            vec![
+                "  a b c ↶    ",
+                " ┌─────┐┌───┐",
+                "1│     ││▶5 │",
+                "2│  1  ││ — │",
+                "3│     ││  0│",
+                " └─────┘└───┘",
+            ],
+            render(&game)
+        );
+    }
+
+    #[test]
+    fn test_no_color() {
+        let mut game = rendered_game(&["...", ".1.", "..."], "5", 0, false);
+        game.cursor = Some(Cursor { x: 0, y: 0 });
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 5));
+        game.color = false;
+        (&game).render(buf.area, &mut buf);
+        strip_colors(&mut buf);
+        assert!(
+            buf.content
+                .iter()
+                .all(|c| c.fg == Color::Reset && c.bg == Color::Reset)
+        );
+        // ~ the stone still stands out, as does the cursor
+        assert!(buf[(3, 2)].modifier.contains(Modifier::BOLD));
+        assert!(buf[(1, 1)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_stone_histogram() {
+        let mut game = rendered_game(&[".....", "..1..", "....."], "55555", 0, false).state;
+        assert_eq!(
+            "placed             \ncleared            \n         0123456789",
+            stone_histogram(&game)
+        );
+        for x in 0..4 {
+            game.place_next(Cursor { x, y: 0 });
+        }
+        let lines: Vec<_> = stone_histogram(&game).lines().map(String::from).collect();
+        assert_eq!("placed        █    ", lines[0]);
+        assert_eq!("cleared            ", lines[1]);
+    }
+
+    #[test]
+    fn test_render_mini() {
+        let mut game = rendered_game(&["1...", ".2..", "...3"], "5", 0, false);
+        game.mini = true;
+        assert_eq!(
+            vec![
+                "┌────┐┌───┐",
+                "│1   ││▶5 │",
+                "│ 2  ││ — │",
+                "│   3││  0│",
+                "└────┘└───┘",
+            ],
+            render(&game)
+        );
+        game.coords = true;
+        game.cursor = Some(Cursor { x: 2, y: 1 });
+        assert_eq!(
+            vec![
+                "  abcd ↶    ",
+                " ┌────┐┌───┐",
+                "1│1   ││▶5 │",
+                "2│ 2  ││ — │",
+                "3│   3││  0│",
+                " └────┘└───┘",
+            ],
+            render(&game)
+        );
+    }
+
+    #[test]
+    fn test_render_trail() {
+        let mut game = rendered_game(&["....", ".12.", "...."], "53", 0, false);
+        // ~ the 3 clears the 1 and the 2, and never occupies its cell
+        game.state.place_next(Cursor { x: 3, y: 2 });
+        game.state.place_next(Cursor { x: 2, y: 0 });
+        // ~ just the board
+        let board = |game: &RenderedGame<_>| -> Vec<String> {
+            render(game)
+                .iter()
+                .map(|l| l.chars().take(9).collect())
+                .collect()
+        };
+        assert_eq!(
+            vec![
+                "┌───────┐",
+                "│       │",
+                "│  · ·  │",
+                "│      5│",
+                "└───────┘",
+            ],
+            board(&game)
+        );
+        game.show_trail = false;
+        assert_eq!("│       │", board(&game)[2]);
+    }
 
-const HELP_LINES: usize = num_lines(HELP_TEXT);
+    #[test]
+    fn test_render_hidden_nexts() {
+        // ~ the preview is cut to the height of the board
+        let board = [".....", "..1..", ".....", ".....", "....."];
+        assert_eq!(
+            vec![
+                "┌─────────┐┌───┐",
+                "│         ││▶5 │",
+                "│    1    ││ ↑ │",
+                "│         ││ 6 │",
+                "│         ││ +4│",
+                "│         ││  0│",
+                "└─────────┘└───┘",
+            ],
+            render(&rendered_game(&board, "567890", 0, false))
+        );
+        assert_eq!(
+            vec![
+                "┌──────────┐┌────┐",
+                "│          ││▶５ │",
+                "│    １    ││  ￪ │",
+                "│          ││ ６ │",
+                "│          ││ +4 │",
+                "│          ││   0│",
+                "└──────────┘└────┘",
+            ],
+            render(&rendered_game(&board, "567890", 0, true))
+        );
+    }
 
-const fn num_lines(s: &str) -> usize {
-    let bytes = s.as_bytes();
-    let mut n = 0;
-    let mut i = 0;
-    while i < bytes.len() {
-        if bytes[i] == b'\n' {
-            n += 1;
+    #[test]
+    fn test_render_cursor() {
+        for (wide, width) in [(false, 1), (true, 2)] {
+            let mut game = rendered_game(&["...", ".1.", "..."], "5", 0, wide);
+            game.cursor = Some(Cursor { x: 2, y: 1 });
+            let (w, h) = game.size();
+            let mut buf = Buffer::empty(Rect::new(0, 0, w, h));
+            (&game).render(buf.area, &mut buf);
+            let highlighted: Vec<_> = buf
+                .content
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.bg == Color::DarkGray)
+                .map(|(i, _)| (i as u16 % w, i as u16 / w))
+                .collect();
+            let expected: Vec<_> = (5..5 + width).map(|x| (x, 2)).collect();
+            assert_eq!(expected, highlighted, "wide: {wide}");
+        }
+    }
+
+    #[test]
+    fn test_render_marker() {
+        let mut game = rendered_game(&["...", ".1.", "..."], "1", 0, false);
+        let marker = Cursor { x: 0, y: 0 };
+        game.marker = Some(marker);
+        assert_eq!(
+            vec![
+                "┌─────┐┌───┐",
+                "│+    ││▶1 │",
+                "│  1  ││ — │",
+                "│     ││  0│",
+                "└─────┘└───┘",
+            ],
+            render(&game)
+        );
+        assert_eq!(
+            "+ clears 1",
+            marker_outcome(&game.state, marker).to_string()
+        );
+        let game = rendered_game(&["...", ".1.", "..."], "2", 0, false);
+        assert_eq!(
+            "+ occupies",
+            marker_outcome(&game.state, marker).to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_marker_outcome() {
+        // ~ next to the par rather than on top of it
+        let mut app = app(&[".........", "....1....", "........."], "5");
+        app.game.marker = Some(Cursor { x: 0, y: 0 });
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buf = terminal.backend().buffer();
+        let lines = buffer_lines(buf);
+        let y = lines.iter().position(|l| l.contains("par")).unwrap();
+        let line = lines[y].trim();
+        assert!(line.starts_with("+ occupies"), "{lines:#?}");
+        assert!(line.ends_with("under par"), "{lines:#?}");
+        let x = lines[y].find("under par").unwrap() as u16;
+        assert_eq!(Color::Green, buf[(x, y as u16)].fg);
+    }
+
+    #[test]
+    fn test_need_have() {
+        let game = rendered_game(&[".....", ".18..", "....."], "5", 0, false);
+        let need = |x, y| need_have(&game.state, Cursor { x, y }).unwrap().to_string();
+        assert_eq!("need …5, have …1", need(0, 0));
+        assert_eq!("need …5, have …9", need(1, 0));
+        assert_eq!("need …5, have …8", need(3, 2));
+        assert_eq!("need …5, have none", need(4, 1));
+    }
+
+    #[test]
+    fn test_set_wide() {
+        let board = ["...", ".1.", "..."];
+        let mut game = rendered_game(&board, "5", 7, false);
+        game.set_wide(true);
+        assert_eq!(render(&rendered_game(&board, "5", 7, true)), render(&game));
+        game.set_wide(false);
+        assert_eq!(render(&rendered_game(&board, "5", 7, false)), render(&game));
+    }
+
+    #[test]
+    fn test_render_sums() {
+        let mut game = rendered_game(&["....", ".18.", "...."], "5", 0, false);
+        game.show_sums = true;
+        assert_eq!(
+            vec![
+                "┌───────┐┌───┐",
+                "│1 9 9 8││▶5 │",
+                "│1 1 8 8││ — │",
+                "│1 9 9 8││  0│",
+                "└───────┘└───┘",
+            ],
+            render(&game)
+        );
+    }
+
+    #[test]
+    fn test_text_screen_scroll() {
+        let lines = |scroll: &mut u16| {
+            let area = Rect::new(0, 0, 12, 6);
+            let mut buf = Buffer::empty(area);
+            TextScreen {
+                title: "T",
+                text: "1\n2\n3\n4\n5\n6\n7\n",
+            }
+            .render(area, &mut buf, scroll);
+            buffer_lines(&buf)
+        };
+        let mut scroll = 0;
+        assert_eq!(
+            vec![
+                "┌────T─────┐",
+                "│     1    █",
+                "│     2    █",
+                "│     3    ║",
+                "│     4    ║",
+                "└── more ↓ ┘",
+            ],
+            lines(&mut scroll)
+        );
+        // ~ scrolling beyond the end stops at the last page
+        scroll = u16::MAX;
+        assert_eq!(
+            vec![
+                "┌────T─────┐",
+                "│     4    ║",
+                "│     5    ║",
+                "│     6    █",
+                "│     7    █",
+                "└──────────┘",
+            ],
+            lines(&mut scroll)
+        );
+        assert_eq!(3, scroll);
+    }
+
+    #[test]
+    fn test_text_screen_wrap() {
+        // ~ centered and capped in width; wrapped at word boundaries
+        let area = Rect::new(0, 0, 80, 5);
+        let mut buf = Buffer::empty(area);
+        let text = "word ".repeat(20);
+        let mut scroll = 5;
+        TextScreen {
+            title: "T",
+            text: &text,
+        }
+        .render(area, &mut buf, &mut scroll);
+        let lines = buffer_lines(&buf);
+        let (line1, line2) = (&lines[1], &lines[2]);
+        assert_eq!(0, scroll);
+        assert_eq!(Some(8), line1.find('│'));
+        assert_eq!(12, line1.matches("word").count());
+        assert_eq!(8, line2.matches("word").count());
+        assert!(lines[4].trim_end().ends_with('┘'));
+        assert!(!lines[4].contains("more"));
+    }
+
+    // ~ the press of a key without modifiers as input to `App::step`
+    fn key(code: KeyCode) -> Option<Event> {
+        Some(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+    }
+
+    // ~ an app playing the given board (see `rendered_game`)
+    fn app(board: &[&str], nexts: &str) -> App<Pcg32, fn(u64) -> Pcg32> {
+        App {
+            game: rendered_game(board, nexts, 0, false),
+            point: Some(Cursor::default()),
+            board_area: Rect::default(),
+            scroll: (0, 0),
+            timer: Timer::default(),
+            new_rng: Pcg32::seed_from_u64 as fn(u64) -> Pcg32,
+            seed: 0,
+            difficulty: Difficulty::Normal,
+            start_score: 0,
+            bag: 0,
+            random_cursor: false,
+            zen: false,
+            goal_clears: 0,
+            reveal_seed: false,
+            resumed: false,
+            assist: false,
+            misses: 0,
+            daily: None,
+            confirm: true,
+            skip_isolated: false,
+            bell: false,
+            show_need: false,
+            key_repeat: None,
+            focused: true,
+            mode: ScreenMode::Playing,
+            return_mode: ScreenMode::Playing,
+            notice: None,
+            notice_until: None,
+            keymap: Keymap::default(),
+            stats: Stats::default(),
+            recorder: None,
+            replay: None,
+            moves: None,
+            playback: None,
+            demo: None,
+            seed_input: String::new(),
+            redraw: false,
+            frame_interval: Duration::from_secs(1) / 30,
+            dirty: true,
+            text_page: 0,
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        // ~ no input just advances the clock
+        assert!(app.step(None));
+        assert!(app.timer.running_since.is_some());
+        assert!(app.step(key(KeyCode::Char('?'))));
+        assert!(matches!(app.mode, ScreenMode::Help(0)));
+        assert!(app.timer.running_since.is_none());
+        assert!(app.step(key(KeyCode::Esc)));
+        assert!(matches!(app.mode, ScreenMode::Playing));
+        assert!(app.step(key(KeyCode::Char(':'))));
+        assert!(matches!(app.mode, ScreenMode::Keys(0)));
+        assert!(app.step(key(KeyCode::Down)));
+        assert!(matches!(app.mode, ScreenMode::Keys(1)));
+        assert!(app.step(key(KeyCode::Esc)));
+        assert!(matches!(app.mode, ScreenMode::Playing));
+        // ~ paused while out of focus
+        assert!(app.step(Some(Event::FocusLost)));
+        assert!(app.timer.running_since.is_none());
+        assert!(app.step(Some(Event::FocusGained)));
+        assert!(app.timer.running_since.is_some());
+        assert!(app.step(key(KeyCode::Char('q'))));
+        assert!(app.step(key(KeyCode::Char('n'))));
+        assert!(app.step(key(KeyCode::Char('q'))));
+        assert!(!app.step(key(KeyCode::Char('y'))));
+    }
+
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(0, exit_status(Some(Finished::Success)));
+        assert_eq!(2, exit_status(Some(Finished::Failure)));
+        assert_eq!(2, exit_status(None));
+    }
+
+    #[test]
+    fn test_render_game_over_small() {
+        let mut app = app(&["...", "...", "..."], "5");
+        app.mode = ScreenMode::GameOver;
+        // ~ the message is taller than the terminal; its start is shown
+        // rather than the board
+        for height in 4..12 {
+            let mut terminal = Terminal::new(TestBackend::new(40, height)).unwrap();
+            terminal.draw(|frame| app.draw(frame)).unwrap();
+            let lines = buffer_lines(terminal.backend().buffer());
+            assert!(
+                lines.iter().any(|l| l.contains("Congratulations!")),
+                "height: {height}: {lines:#?}"
+            );
+        }
+        // ~ centered with a blank row above and below if there's room
+        let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        let top = lines
+            .iter()
+            .position(|l| l.contains("Congratulations!"))
+            .unwrap();
+        assert!((4..15).contains(&top), "{lines:#?}");
+        assert_eq!("", lines[top - 1].trim());
+    }
+
+    #[test]
+    fn test_board_lines() {
+        let mut input = "...\n.1.\n...\n2,2\nquit\n".as_bytes();
+        assert_eq!("...\n.1.\n...\n", board_lines(&mut input, 3).unwrap());
+        assert_eq!(b"2,2\nquit\n", input);
+        // ~ too few lines are left to `load_from_reader` to complain about
+        let mut input = "...\n.1.".as_bytes();
+        assert_eq!("...\n.1.", board_lines(&mut input, 3).unwrap());
+    }
+
+    #[test]
+    fn test_center_cursor() {
+        let mut app = app(&[".....", ".1.1.", ".....", "....."], "5");
+        app.point = Some(Cursor { x: 4, y: 3 });
+        assert!(app.step(key(KeyCode::Home)));
+        assert_eq!(Some(Cursor { x: 2, y: 2 }), app.point);
+        // ~ next to the middle if it's taken
+        let mut app = self::app(&[".....", ".111.", ".111.", "....."], "5");
+        assert!(app.step(key(KeyCode::Home)));
+        let point = app.point.unwrap();
+        assert_eq!(None, app.game.state.cell(point));
+        assert!(point.x.abs_diff(2) <= 2 && point.y.abs_diff(2) <= 1);
+    }
+
+    #[test]
+    fn test_frame_timeout() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        // ~ the clock ticks by the second
+        assert!(app.step(None));
+        assert!(app.dirty);
+        assert!(app.frame_timeout() <= Duration::from_secs(1));
+        // ~ nothing changes on the help screen on its own
+        assert!(app.step(key(KeyCode::Char('?'))));
+        app.dirty = false;
+        assert!(app.step(None));
+        assert!(!app.dirty);
+        assert_eq!(IDLE_TIMEOUT, app.frame_timeout());
+        // ~ but by any input
+        assert!(app.step(key(KeyCode::Down)));
+        assert!(app.dirty);
+        // ~ animations are capped by the frame rate
+        app.game.flash = Some(Flash {
+            cell: Cursor::default(),
+            started: Instant::now(),
+            duration: Flash::DURATION,
+        });
+        assert!(app.frame_timeout() <= Flash::DURATION);
+        // ~ just for the time left of it
+        app.game.flash = Some(Flash {
+            cell: Cursor::default(),
+            started: Instant::now() - Duration::from_millis(1000),
+            duration: Flash::ASSIST_DURATION,
+        });
+        let timeout = app.frame_timeout();
+        assert!(timeout <= Duration::from_millis(500), "{timeout:?}");
+        app.frame_interval = Duration::from_secs(1);
+        assert_eq!(Duration::from_secs(1), app.frame_timeout());
+    }
+
+    #[test]
+    fn test_assist() {
+        let board = [".....", ".....", ".....", ".....", "....5"];
+        let mut app = app(&board, "5555");
+        app.assist = true;
+        let misses = [
+            Cursor { x: 0, y: 0 },
+            Cursor { x: 2, y: 0 },
+            Cursor { x: 0, y: 2 },
+        ];
+        for (i, point) in misses.into_iter().enumerate() {
+            app.point = Some(point);
+            assert!(app.step(key(KeyCode::Char(' '))));
+            assert_eq!(i + 1, app.misses);
+            let flash = app.game.flash.as_ref().unwrap();
+            if i < 2 {
+                assert_eq!(point, flash.cell);
+            } else {
+                // ~ pointing out the nearest clearing place
+                let target = app.game.state.find_nearest_clearing(point);
+                assert_eq!(target, Some(flash.cell));
+                assert_eq!(Flash::ASSIST_DURATION, flash.duration);
+                assert!(app.notice.as_deref().unwrap().contains("flashing"));
+            }
+        }
+        // ~ which outlasts moving the cursor towards it
+        assert!(app.step(key(KeyCode::Up)));
+        assert!(app.game.flash.as_ref().is_some_and(Flash::is_assist));
+        // ~ taken back by an undo
+        assert!(app.step(key(KeyCode::Char('u'))));
+        assert_eq!(
+            (0, None),
+            (app.misses, app.game.flash.as_ref().map(|f| f.cell))
+        );
+        assert!(app.step(key(KeyCode::Char('r'))));
+        app.misses = misses.len();
+        app.game.flash = Some(Flash {
+            cell: app.game.state.find_nearest_clearing(misses[2]).unwrap(),
+            started: Instant::now(),
+            duration: Flash::ASSIST_DURATION,
+        });
+        // ~ counted anew by a clearing placement
+        app.point = app.game.flash.as_ref().map(|f| f.cell);
+        assert!(app.step(key(KeyCode::Char(' '))));
+        assert!(app.game.clearing.is_some());
+        assert!(app.game.flash.is_none());
+        assert_eq!(0, app.misses);
+
+        // ~ strictly opt-in
+        let mut app = self::app(&board, "5555");
+        for point in misses {
+            app.point = Some(point);
+            assert!(app.step(key(KeyCode::Char(' '))));
+            assert_eq!(Some(point), app.game.flash.as_ref().map(|f| f.cell));
+        }
+    }
+
+    #[test]
+    fn test_seed_entry() {
+        let mut app = app(&["...", "...", "..."], "5");
+        app.mode = ScreenMode::SeedEntry;
+        for (c, modifiers) in [
+            ('4', KeyModifiers::NONE),
+            ('c', KeyModifiers::CONTROL),
+            ('1', KeyModifiers::ALT),
+            ('2', KeyModifiers::SHIFT),
+        ] {
+            assert!(app.step(Some(Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers)))));
+        }
+        // ~ just the typed characters
+        assert_eq!("42", app.seed_input);
+        assert!(matches!(app.mode, ScreenMode::SeedEntry));
+    }
+
+    #[test]
+    fn test_placement_status() {
+        let mut app = app(&["....", ".1..", "...."], "52");
+        app.point = Some(Cursor { x: 0, y: 0 });
+        assert!(app.step(key(KeyCode::Char(' '))));
+        assert_eq!(Some("Placed 5 → stays."), app.notice.as_deref());
+        // ~ stays for a while
+        assert!(app.step(None));
+        assert!(app.notice.is_some());
+        // ~ and goes away on its own
+        app.notice_until = Some(Instant::now());
+        assert!(app.step(None));
+        assert_eq!(None, app.notice);
+        // ~ or by the next key
+        app.point = Some(Cursor { x: 3, y: 2 });
+        assert!(app.step(key(KeyCode::Char(' '))));
+        assert!(app.notice.is_some());
+        assert!(app.step(key(KeyCode::Left)));
+        assert_eq!(None, app.notice);
+    }
+
+    #[test]
+    fn test_demo() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        app.new_game_with_seed(3);
+        app.demo = Some(Instant::now() - DEMO_STEP);
+        // ~ a move per step once due
+        assert!(app.step(None));
+        assert_eq!(1, app.game.state.num_placed());
+        assert!(app.step(None));
+        assert_eq!(1, app.game.state.num_placed());
+        // ~ plays on into a new game; never over
+        for _ in 0..1000 {
+            app.game.clearing = None;
+            app.demo = Some(Instant::now() - DEMO_STEP);
+            assert!(app.step(None));
+            assert!(matches!(app.mode, ScreenMode::Playing));
+        }
+        assert_ne!(3, app.seed);
+        // ~ any key hands the game over to the player
+        let num_placed = app.game.state.num_placed();
+        assert!(app.step(key(KeyCode::Left)));
+        assert!(app.demo.is_none());
+        assert_eq!(num_placed, app.game.state.num_placed());
+        assert!(app.game.state.is_finished().is_none());
+    }
+
+    #[test]
+    fn test_play_back() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        // ~ wins the game of the given seed by the moves of a simple
+        // strategy; bypassing the game-over screen (and thus the
+        // statistics)
+        let win = |app: &mut App<_, _>, seed| {
+            app.new_game_with_seed(seed);
+            while app.game.state.is_finished().is_none() {
+                let game = &app.game.state;
+                let point = game.find_hint().or_else(|| game.free_cells().next());
+                let point = point.unwrap();
+                app.record(Step::Place(point));
+                app.game.state.place_next(point);
+            }
+            matches!(app.game.state.is_finished(), Some(Finished::Success))
+        };
+        assert!((0..100).any(|seed| win(&mut app, seed)));
+        let num_placed = app.game.state.num_placed();
+
+        app.play_back();
+        assert!(matches!(app.mode, ScreenMode::Replay));
+        assert_eq!(0, app.game.state.num_placed());
+        // ~ a move per step once due
+        assert!(app.step(None));
+        assert_eq!(0, app.game.state.num_placed());
+        app.playback = Some(Instant::now() - PLAYBACK_STEP);
+        assert!(app.step(None));
+        assert_eq!(1, app.game.state.num_placed());
+        // ~ any key skips to the end
+        assert!(app.step(key(KeyCode::Char('x'))));
+        assert!(matches!(app.mode, ScreenMode::GameOver));
+        assert!(app.playback.is_none());
+        assert_eq!(num_placed, app.game.state.num_placed());
+        assert_eq!(0, app.game.state.num_remaining());
+    }
+
+    #[test]
+    fn test_draw_seed_info() {
+        // ~ a game over with a long seed on a narrow terminal
+        let mut app = app(&["...", "...", "..."], "5");
+        app.mode = ScreenMode::GameOver;
+        app.seed = u64::MAX;
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert_eq!(" quit | new g 18446744073709551615 00:00", lines[11]);
+        // ~ nor is the seed of a resumed game known
+        app.resumed = true;
+        app.reveal_seed = true;
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines.iter().all(|l| !l.contains("18446744073709551615")));
+        assert!(lines[11].ends_with(" 00:00"), "{lines:#?}");
+        app.resumed = false;
+
+        // ~ just the time while playing
+        app.game = rendered_game(&["...", ".1.", "..."], "5", 0, false);
+        app.mode = ScreenMode::Playing;
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert_eq!(" quit | ? help | Stats | undo | ←↑ 00:00", lines[11]);
+    }
+
+    #[test]
+    fn test_draw_hints_as_bound() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        app.keymap =
+            Keymap::from_config("quit = 'ctrl-c'\nundo = ['z', 'u']\nstats = []\nmove_west = 'a'")
+                .unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert_eq!(
+            " <ctrl-c> quit | ? help | z undo | a ↑ ↓ → <space>     00:00",
+            lines[11]
+        );
+        app.mode = ScreenMode::Help(0);
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(
+            lines[11].starts_with(" <ctrl-c>/esc close | ↑↓ pgup/pgdn home/end scroll | : keys"),
+            "{lines:#?}"
+        );
+        // ~ the overview of the keys is at hand from the help
+        assert!(app.step(key(KeyCode::Char(':'))));
+        assert!(matches!(app.mode, ScreenMode::Keys(0)));
+    }
+
+    #[test]
+    fn test_coords_line_up() {
+        let mut app = app(&["...", ".1.", "..."], "5");
+        app.game.coords = true;
+        app.point = Some(Cursor { x: 2, y: 0 });
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines: Vec<Vec<char>> = buffer_lines(terminal.backend().buffer())
+            .iter()
+            .map(|l| l.chars().collect())
+            .collect();
+        // ~ the cursor is in the column labeled 'c' and the row '1'
+        let cursor = terminal.backend_mut().get_cursor_position().unwrap();
+        let label_row = lines.iter().position(|l| l.contains(&'a')).unwrap();
+        assert_eq!('c', lines[label_row][cursor.x as usize]);
+        assert_eq!(label_row + 2, cursor.y as usize);
+        assert_eq!('1', lines[cursor.y as usize][cursor.x as usize - 6]);
+        // ~ clicking at column 'a' in row '3' moves the cursor there
+        let x = lines[label_row].iter().position(|&c| c == 'a').unwrap();
+        app.handle_mouse_event(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: x as u16,
+            row: cursor.y + 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(Some(Cursor { x: 0, y: 2 }), app.point);
+
+        // ~ likewise with one column per cell
+        app.game.mini = true;
+        app.point = Some(Cursor { x: 2, y: 0 });
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let lines = buffer_lines(terminal.backend().buffer());
+        let label_row = lines.iter().position(|l| l.contains("abc")).unwrap();
+        let x = lines[label_row].chars().position(|c| c == 'c').unwrap();
+        let cursor = terminal.backend_mut().get_cursor_position().unwrap();
+        assert_eq!((x, label_row + 2), (cursor.x as usize, cursor.y as usize));
+        app.handle_mouse_event(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: x as u16 - 1,
+            row: cursor.y + 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(Some(Cursor { x: 1, y: 2 }), app.point);
+    }
+
+    #[test]
+    fn test_scroll_to() {
+        // ~ stays put while in view
+        assert_eq!(3, scroll_to(3, 4, 6, 5, 20));
+        // ~ scrolls back / forth just enough
+        assert_eq!(2, scroll_to(3, 2, 4, 5, 20));
+        assert_eq!(5, scroll_to(3, 7, 10, 5, 20));
+        // ~ never beyond the end
+        assert_eq!(15, scroll_to(3, 19, 22, 5, 20));
+        assert_eq!(0, scroll_to(3, 4, 6, 30, 20));
+    }
+
+    #[test]
+    fn test_wrapped_line_count() {
+        assert_eq!(2, wrapped_line_count("ab cd ef", 5));
+        assert_eq!(3, wrapped_line_count("ab\n\ncd", 5));
+        assert_eq!(3, wrapped_line_count("abcdefghijk", 5));
+        // ~ runs of spaces count in full within a line
+        assert_eq!(1, wrapped_line_count("ab  cd", 6));
+        assert_eq!(2, wrapped_line_count("ab  cd", 5));
+        // ~ as many lines as rendered by ratatui
+        for width in [20, 41, TextScreen::MAX_WIDTH - 2] {
+            let mut buf = Buffer::empty(Rect::new(0, 0, width, 1000));
+            Paragraph::new(HELP_TEXT)
+                .wrap(Wrap { trim: true })
+                .render(buf.area, &mut buf);
+            let rendered = buffer_lines(&buf)
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .unwrap()
+                + 1;
+            assert_eq!(
+                rendered,
+                wrapped_line_count(HELP_TEXT, width),
+                "width: {width}"
+            );
         }
-        i += 1;
     }
-    n
 }
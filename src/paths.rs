@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+/// The file a game in progress is saved to and resumed from.
+pub fn save_file() -> Option<PathBuf> {
+    data_dir().map(|d| d.join("save"))
+}
+
+/// The file the statistics over all finished games are kept in.
+pub fn stats_file() -> Option<PathBuf> {
+    data_dir().map(|d| d.join("stats.toml"))
+}
+
+/// The file holding the defaults of the command line options.
+pub fn options_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("summing.toml"))
+}
+
+/// The file configuring the key bindings.
+pub fn keys_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("keys.toml"))
+}
+
+// ~ the directory of the game's configuration files; following the
+// XDG base directory specification with a fallback for windows
+fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|d| PathBuf::from(d).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .map(|d| d.join(env!("CARGO_PKG_NAME")))
+}
+
+// ~ the directory to maintain the game's data in; following the XDG
+// base directory specification with a fallback for windows
+fn data_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|d| PathBuf::from(d).join(".local/share")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .map(|d| d.join(env!("CARGO_PKG_NAME")))
+}
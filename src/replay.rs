@@ -0,0 +1,352 @@
+//! Recording and replaying of games. A recording is a line based
+//! text file: a header describing how the game was set up followed by
+//! the moves made, one per line, e.g.
+//!
+//! ```text
+//! summing-replay 1
+//! seed 42
+//! size 9 9
+//! difficulty normal
+//! preview 4
+//! base 10
+//! neighbours all
+//! start-score 0
+//! bag 0
+//! cursor random
+//! zen false
+//! goal-clears 0
+//! 3 4
+//! undo
+//! 5 1
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::game::{Cursor, Difficulty, MAX_SIZE, MIN_SIZE, NeighbourMode};
+
+// ~ the first line of a recording; identifies the format and its
+// version
+const REPLAY_HEADER: &str = "summing-replay 1";
+
+/// Everything needed to set up the very same game again
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub seed: u64,
+    pub rows: usize,
+    pub cols: usize,
+    pub difficulty: Difficulty,
+    pub preview: usize,
+    pub base: usize,
+    pub neighbours: NeighbourMode,
+    pub start_score: usize,
+    /// The number of each stone in the finite bag; zero for an
+    /// endless stream of stones
+    pub bag: usize,
+    /// Whether the cursor started at a random cell; see
+    /// `Game::initial_cursor`
+    pub random_cursor: bool,
+    /// Whether the game was played in zen mode; see `Game::set_zen`
+    pub zen: bool,
+    /// The number of stones to clear to win the game; zero to win by
+    /// clearing the board (see `Game::set_goal_clears`)
+    pub goal_clears: usize,
+}
+
+/// A single move of a game
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// The next stone was placed at the given cell
+    Place(Cursor),
+    Undo,
+    Redo,
+}
+
+/// Writes the moves of a game to a file as they are made
+pub struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    /// Creates (or truncates) the file at `path` and writes the
+    /// header of a recording to it.
+    pub fn create(path: &Path, header: &Header) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        let mut r = Recorder { file };
+        r.restart(header)?;
+        Ok(r)
+    }
+
+    /// Discards everything recorded so far and starts over with the
+    /// given header.
+    pub fn restart(&mut self, header: &Header) -> io::Result<()> {
+        self.file.set_len(0)?;
+        io::Seek::rewind(&mut self.file)?;
+        write_header(&mut self.file, header)
+    }
+
+    /// Appends a move to the recording.
+    pub fn record(&mut self, step: Step) -> io::Result<()> {
+        write_step(&mut self.file, step)
+    }
+}
+
+/// A recorded game being replayed step by step
+pub struct Replay {
+    pub header: Header,
+    steps: Vec<Step>,
+    // ~ the index of the next step to replay
+    pos: usize,
+}
+
+impl Replay {
+    /// Replays the given steps of the game set up as per `header`.
+    pub fn new(header: Header, steps: Vec<Step>) -> Self {
+        Replay {
+            header,
+            steps,
+            pos: 0,
+        }
+    }
+
+    /// Loads the recording at `path`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let f = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        Self::from_reader(io::BufReader::new(f))
+            .with_context(|| format!("failed to load replay from {}", path.display()))
+    }
+
+    fn from_reader<R: BufRead>(rdr: R) -> Result<Self> {
+        let mut lines = rdr.lines();
+        let mut next_line = || match lines.next() {
+            Some(line) => Ok(line?),
+            None => Err(anyhow::anyhow!("unexpected end of file")),
+        };
+        let header = next_line()?;
+        if header != REPLAY_HEADER {
+            bail!("unsupported replay format: {header:?}");
+        }
+        // ~ reads the "key value..." header line expected next
+        let mut field = |key: &str| -> Result<String> {
+            let line = next_line()?;
+            match line.split_once(' ') {
+                Some((k, v)) if k == key => Ok(v.to_owned()),
+                _ => bail!("expected '{key}' but got: {line:?}"),
+            }
+        };
+        let seed = field("seed")?.parse()?;
+        let (rows, cols) = match field("size")?.split_once(' ') {
+            Some((rows, cols)) => (rows.parse::<usize>()?, cols.parse::<usize>()?),
+            None => bail!("invalid board dimensions"),
+        };
+        if rows < MIN_SIZE || cols < MIN_SIZE {
+            bail!("board too small: {rows}x{cols}");
+        }
+        if rows > MAX_SIZE || cols > MAX_SIZE {
+            bail!("board too large: {rows}x{cols}");
+        }
+        let difficulty = field("difficulty")?;
+        let Some(difficulty) = Difficulty::ALL.into_iter().find(|d| d.name() == difficulty) else {
+            bail!("invalid difficulty: {difficulty:?}");
+        };
+        let preview = field("preview")?.parse()?;
+        if !(1..=crate::game::MAX_NEXTS).contains(&preview) {
+            bail!("invalid preview: {preview}");
+        }
+
+        // ~ optional fields; missing in recordings made by earlier
+        // versions
+        let (mut base, mut neighbours, mut start_score, mut bag, mut random_cursor, mut zen) = (
+            crate::game::MAX_BASE,
+            NeighbourMode::All,
+            0,
+            0,
+            false,
+            false,
+        );
+        let mut goal_clears = 0;
+        let mut first = lines.next().transpose()?;
+        while let Some(line) = &first {
+            if let Some(n) = line.strip_prefix("base ") {
+                base = n.parse()?;
+                if !(crate::game::MIN_BASE..=crate::game::MAX_BASE).contains(&base) {
+                    bail!("invalid base: {base}");
+                }
+            } else if let Some(name) = line.strip_prefix("neighbours ") {
+                let Some(mode) = NeighbourMode::ALL.into_iter().find(|m| m.name() == name) else {
+                    bail!("invalid neighbour mode: {name:?}");
+                };
+                neighbours = mode;
+            } else if let Some(n) = line.strip_prefix("start-score ") {
+                start_score = n.parse()?;
+            } else if let Some(n) = line.strip_prefix("bag ") {
+                bag = n.parse()?;
+            } else if let Some(cursor) = line.strip_prefix("cursor ") {
+                random_cursor = match cursor {
+                    "random" => true,
+                    "origin" => false,
+                    _ => bail!("invalid cursor: {cursor:?}"),
+                };
+            } else if let Some(z) = line.strip_prefix("zen ") {
+                zen = z.parse()?;
+            } else if let Some(n) = line.strip_prefix("goal-clears ") {
+                goal_clears = n.parse()?;
+            } else {
+                break;
+            }
+            first = lines.next().transpose()?;
+        }
+
+        let mut steps = Vec::new();
+        for line in first.into_iter().map(io::Result::Ok).chain(lines) {
+            let line = line?;
+            let step = match line.as_str() {
+                "undo" => Step::Undo,
+                "redo" => Step::Redo,
+                _ => match line.split_once(' ') {
+                    Some((x, y)) => match (x.parse::<u8>(), y.parse::<u8>()) {
+                        (Ok(x), Ok(y)) if (x as usize) < cols && (y as usize) < rows => {
+                            Step::Place(Cursor { x, y })
+                        }
+                        _ => bail!("invalid move: {line:?}"),
+                    },
+                    None => bail!("invalid move: {line:?}"),
+                },
+            };
+            steps.push(step);
+        }
+        Ok(Replay {
+            header: Header {
+                seed,
+                rows,
+                cols,
+                difficulty,
+                preview,
+                base,
+                neighbours,
+                start_score,
+                bag,
+                random_cursor,
+                zen,
+                goal_clears,
+            },
+            steps,
+            pos: 0,
+        })
+    }
+
+    /// Advances the replay returning the step to make next, if any.
+    pub fn next_step(&mut self) -> Option<Step> {
+        let step = self.steps.get(self.pos).copied()?;
+        self.pos += 1;
+        Some(step)
+    }
+
+    /// Peeks at the step to be returned by the next `next_step`.
+    pub fn peek_step(&self) -> Option<Step> {
+        self.steps.get(self.pos).copied()
+    }
+
+    /// The number of steps replayed so far along with the total
+    /// number of steps.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.pos, self.steps.len())
+    }
+}
+
+fn write_header<W: Write>(w: &mut W, h: &Header) -> io::Result<()> {
+    writeln!(w, "{REPLAY_HEADER}")?;
+    writeln!(w, "seed {}", h.seed)?;
+    writeln!(w, "size {} {}", h.rows, h.cols)?;
+    writeln!(w, "difficulty {}", h.difficulty.name())?;
+    writeln!(w, "preview {}", h.preview)?;
+    writeln!(w, "base {}", h.base)?;
+    writeln!(w, "neighbours {}", h.neighbours.name())?;
+    writeln!(w, "start-score {}", h.start_score)?;
+    writeln!(w, "bag {}", h.bag)?;
+    writeln!(
+        w,
+        "cursor {}",
+        if h.random_cursor { "random" } else { "origin" }
+    )?;
+    writeln!(w, "zen {}", h.zen)?;
+    writeln!(w, "goal-clears {}", h.goal_clears)
+}
+
+fn write_step<W: Write>(w: &mut W, step: Step) -> io::Result<()> {
+    match step {
+        Step::Place(p) => writeln!(w, "{} {}", p.x, p.y),
+        Step::Undo => writeln!(w, "undo"),
+        Step::Redo => writeln!(w, "redo"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Header, Replay, Step, write_header, write_step};
+    use crate::game::{Cursor, Difficulty, NeighbourMode};
+
+    #[test]
+    fn test_roundtrip() {
+        let header = Header {
+            seed: 42,
+            rows: 5,
+            cols: 7,
+            difficulty: Difficulty::Hard,
+            preview: 2,
+            base: 6,
+            neighbours: NeighbourMode::Diagonal,
+            start_score: 10,
+            bag: 3,
+            random_cursor: true,
+            zen: true,
+            goal_clears: 20,
+        };
+        let steps = [
+            Step::Place(Cursor { x: 6, y: 4 }),
+            Step::Undo,
+            Step::Redo,
+            Step::Place(Cursor { x: 0, y: 0 }),
+        ];
+        let mut w = Vec::new();
+        write_header(&mut w, &header).unwrap();
+        for step in steps {
+            write_step(&mut w, step).unwrap();
+        }
+        let mut replay = Replay::from_reader(w.as_slice()).unwrap();
+        assert_eq!(header, replay.header);
+        assert_eq!((0, 4), replay.progress());
+        assert_eq!(Some(steps[0]), replay.peek_step());
+        let replayed: Vec<_> = std::iter::from_fn(|| replay.next_step()).collect();
+        assert_eq!(steps.as_slice(), replayed);
+        assert_eq!((4, 4), replay.progress());
+
+        // ~ moves must stay on the board
+        let mut w = Vec::new();
+        write_header(&mut w, &header).unwrap();
+        write_step(&mut w, Step::Place(Cursor { x: 7, y: 0 })).unwrap();
+        assert!(Replay::from_reader(w.as_slice()).is_err());
+
+        // ~ the base, start score, bag, cursor, zen, and goal are
+        // optional
+        let r = "summing-replay 1\nseed 1\nsize 3 3\ndifficulty easy\npreview 1\n0 0\n";
+        let mut replay = Replay::from_reader(r.as_bytes()).unwrap();
+        let h = replay.header;
+        assert_eq!((10, 0, 0), (h.base, h.start_score, h.bag));
+        assert!(!h.random_cursor && !h.zen);
+        assert_eq!(0, h.goal_clears);
+        assert_eq!(Some(Step::Place(Cursor { x: 0, y: 0 })), replay.next_step());
+
+        // ~ the size is bounded by what the game supports
+        for size in ["2 3", "3 256", "300 300"] {
+            let r = format!("summing-replay 1\nseed 1\nsize {size}\ndifficulty easy\npreview 1\n");
+            assert!(Replay::from_reader(r.as_bytes()).is_err(), "{size}");
+        }
+    }
+}
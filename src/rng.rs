@@ -0,0 +1,104 @@
+//! The random number generator driving the games. Unlike `rand`'s
+//! `StdRng`, whose algorithm may change between releases, this one is
+//! fixed such that a seed reproduces the very same game on any
+//! platform with any version of the game.
+
+use rand::{RngCore, SeedableRng};
+
+/// The PCG-XSH-RR 64/32 generator ("pcg32") as described by
+/// <https://www.pcg-random.org>
+#[derive(Clone, Debug)]
+pub struct Pcg32 {
+    state: u64,
+    // ~ selects the stream; always odd
+    inc: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6364136223846793005;
+    // ~ the stream used when seeding from a plain number
+    const DEFAULT_STREAM: u64 = 0xcafe_f00d_d15e_a5e5;
+
+    /// Creates a generator from an initial state and a stream
+    /// selector; equivalent to `pcg32_srandom_r` of the reference
+    /// implementation.
+    pub fn new(state: u64, stream: u64) -> Self {
+        let mut rng = Pcg32 {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(state);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.inc);
+    }
+}
+
+impl RngCore for Pcg32 {
+    fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        let hi = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+impl SeedableRng for Pcg32 {
+    // ~ the initial state followed by the stream selector; both in
+    // little endian
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let (state, stream) = seed.split_at(8);
+        Self::new(
+            u64::from_le_bytes(state.try_into().unwrap()),
+            u64::from_le_bytes(stream.try_into().unwrap()),
+        )
+    }
+
+    // ~ overridden to not depend on the seed expansion provided by
+    // `rand`, which is subject to change
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state, Self::DEFAULT_STREAM)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::Pcg32;
+
+    #[test]
+    fn test_reference_output() {
+        // ~ the first numbers of the reference implementation's demo
+        let mut rng = Pcg32::new(42, 54);
+        let xs: Vec<u32> = (0..6).map(|_| rng.next_u32()).collect();
+        assert_eq!(
+            vec![
+                0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e
+            ],
+            xs
+        );
+    }
+}
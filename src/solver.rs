@@ -0,0 +1,61 @@
+//! A simple, greedy strategy playing games on its own; serves as a
+//! benchmark for the board generation and placement logic.
+
+use rand::Rng;
+
+use crate::game::{Cursor, Finished, Game};
+
+// ~ the max number of placements before giving up on a game which
+// neither clears nor fills up
+const MAX_PLACEMENTS: usize = 100_000;
+
+/// Plays `game` till its end; placing the next stone such that it
+/// clears its neighbours whenever possible, and otherwise onto the
+/// free cell with the fewest neighbours to not get in the way of later
+/// clearances. Returns `None` if the game didn't come to an end
+/// within a sane number of placements.
+pub fn solve<R: Rng>(game: &mut Game<R>) -> Option<Finished> {
+    while game.num_placed() < MAX_PLACEMENTS {
+        if let Some(finished) = game.is_finished() {
+            return Some(finished);
+        }
+        let point = next_move(game)?;
+        game.place_next(point);
+    }
+    None
+}
+
+/// Picks the cell to place the next stone onto by the strategy of
+/// `solve`; `None` if there's no cell to place it onto.
+pub fn next_move<R>(game: &Game<R>) -> Option<Cursor> {
+    game.find_hint().or_else(|| least_crowded(game))
+}
+
+// ~ the free cell with the fewest occupied neighbours; the first such
+// in reading order; but none without neighbours if the game refuses
+// placements onto such
+fn least_crowded<R>(game: &Game<R>) -> Option<Cursor> {
+    game.free_cells()
+        .map(|p| (p, game.neighbour_sum(p).0))
+        .filter(|&(_, cnt)| cnt > 0 || game.allows_isolated_placement())
+        .min_by_key(|&(_, cnt)| cnt)
+        .map(|(p, _)| p)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::solve;
+    use crate::{game::Game, rng::Pcg32};
+
+    #[test]
+    fn test_solve() {
+        for seed in 0..10 {
+            let mut game = Game::new(Pcg32::seed_from_u64(seed));
+            assert!(solve(&mut game).is_some(), "seed {seed}");
+            assert!(game.is_finished().is_some());
+            assert!(game.num_placed() > 0);
+        }
+    }
+}
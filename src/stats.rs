@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+use anyhow::{Context, Result};
+
+use crate::{config, game::Finished, paths};
+
+/// Lifetime statistics over all finished games
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub played: u64,
+    pub won: u64,
+    // ~ the fewest placements any won game took
+    pub best: Option<u64>,
+    // ~ the placements of all won games summed up; for the average
+    pub won_placed: u64,
+    // ~ the time spent on all finished games
+    pub time: Duration,
+    // ~ the fewest placements of the won daily puzzles; by the seed
+    // of the day
+    pub daily: BTreeMap<u64, u64>,
+}
+
+impl Stats {
+    /// Loads the statistics from the user's data directory; empty if
+    /// there are none so far.
+    pub fn load() -> Result<Self> {
+        let Some(path) = paths::stats_file() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).with_context(|| format!("{}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+        }
+    }
+
+    /// Writes the statistics to the user's data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = paths::stats_file().context("no location for statistics")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, self.to_config())?;
+        Ok(())
+    }
+
+    /// Accounts for a game which finished in the given state.
+    pub fn record(&mut self, finished: &Finished, placed: usize, time: Duration) {
+        self.played += 1;
+        self.time += time;
+        if let Finished::Success = finished {
+            let placed = placed as u64;
+            self.won += 1;
+            self.won_placed += placed;
+            self.best = Some(self.best.map_or(placed, |b| b.min(placed)));
+        }
+    }
+
+    /// Accounts for a won daily puzzle (in addition to `record`); the
+    /// fewest placements are kept per day.
+    pub fn record_daily(&mut self, seed: u64, placed: usize) {
+        let placed = placed as u64;
+        self.daily
+            .entry(seed)
+            .and_modify(|b| *b = (*b).min(placed))
+            .or_insert(placed);
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let mut stats = Self::default();
+        for entry in config::parse(text)? {
+            let value = || -> Result<u64> {
+                match entry.values.as_slice() {
+                    [v] => Ok(v.parse()?),
+                    _ => anyhow::bail!("expected a single value"),
+                }
+            };
+            let value = value().with_context(|| format!("line {}", entry.line))?;
+            match entry.key.as_str() {
+                "played" => stats.played = value,
+                "won" => stats.won = value,
+                "best" => stats.best = Some(value),
+                "won_placed" => stats.won_placed = value,
+                "time" => stats.time = Duration::from_secs(value),
+                // ~ the `[daily]` table; ignoring what we don't know
+                // (anymore)
+                key => {
+                    if let Some(Ok(seed)) = key.strip_prefix("daily.").map(str::parse) {
+                        stats.daily.insert(seed, value);
+                    }
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    fn to_config(&self) -> String {
+        let mut s = format!(
+            "played = {}\nwon = {}\nwon_placed = {}\ntime = {}\n",
+            self.played,
+            self.won,
+            self.won_placed,
+            self.time.as_secs()
+        );
+        if let Some(best) = self.best {
+            s.push_str(&format!("best = {best}\n"));
+        }
+        if !self.daily.is_empty() {
+            s.push_str("\n[daily]\n");
+            for (seed, placed) in &self.daily {
+                s.push_str(&format!("{seed} = {placed}\n"));
+            }
+        }
+        s
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Games played: {}", self.played)?;
+        write!(f, "Games won: {}", self.won)?;
+        if let Some(rate) = (self.won * 100).checked_div(self.played) {
+            write!(f, " ({rate}%)")?;
+        }
+        writeln!(f)?;
+        if let Some(best) = self.best {
+            writeln!(f, "Fewest placements: {best}")?;
+            writeln!(
+                f,
+                "Average placements: {:.1}",
+                self.won_placed as f64 / self.won as f64
+            )?;
+        }
+        if !self.daily.is_empty() {
+            writeln!(f, "Daily puzzles won: {}", self.daily.len())?;
+        }
+        let secs = self.time.as_secs();
+        write!(
+            f,
+            "Time played: {}:{:02}:{:02}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+    use crate::game::Finished;
+
+    #[test]
+    fn test_record_roundtrip() {
+        let mut stats = Stats::default();
+        stats.record(&Finished::Failure, 80, Duration::from_secs(100));
+        stats.record(&Finished::Success, 50, Duration::from_secs(200));
+        stats.record(&Finished::Success, 40, Duration::from_secs(300));
+        assert_eq!(
+            Stats {
+                played: 3,
+                won: 2,
+                best: Some(40),
+                won_placed: 90,
+                time: Duration::from_secs(600),
+                daily: Default::default(),
+            },
+            stats
+        );
+        assert_eq!(stats, Stats::parse(&stats.to_config()).unwrap());
+        assert_eq!(Stats::default(), Stats::parse("").unwrap());
+    }
+
+    #[test]
+    fn test_record_daily() {
+        let mut stats = Stats::default();
+        stats.record_daily(20261016, 50);
+        stats.record_daily(20261016, 40);
+        stats.record_daily(20261016, 45);
+        stats.record_daily(20261017, 60);
+        assert_eq!(
+            vec![(20261016, 40), (20261017, 60)],
+            stats
+                .daily
+                .iter()
+                .map(|(&k, &v)| (k, v))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(stats, Stats::parse(&stats.to_config()).unwrap());
+        assert!(stats.to_string().contains("Daily puzzles won: 2"));
+    }
+}
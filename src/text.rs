@@ -0,0 +1,307 @@
+//! A plain text frontend, e.g. for use with a screen reader: the game
+//! is played by typing commands - one per line - and every move is
+//! described in a sentence or two instead of drawing the board.
+
+use std::io::{self, BufRead, Write};
+
+use rand::Rng;
+
+use crate::game::{Cursor, Finished, Game, PlaceOutcome};
+
+const HELP: &str = "\
+Commands:
+  ROW,COL  place the next number at the given row and column (from 1)
+  board    describe the board row by row
+  hint     suggest a place where the next number clears its neighbours
+  undo     take back the last placement
+  help     show this help
+  quit     stop playing";
+
+/// Plays `game` by the commands read from `input` describing the
+/// course of the game to `output`.  Returns how the game finished,
+/// or `None` if it was quit (or the input ended) before.
+pub fn play<R: Rng, I: BufRead, W: Write>(
+    game: &mut Game<R>,
+    input: I,
+    output: &mut W,
+) -> io::Result<Option<Finished>> {
+    writeln!(
+        output,
+        "Clear the board of {} rows and {} columns; type 'help' for the commands.",
+        game.rows(),
+        game.cols()
+    )?;
+    describe_board(game, output)?;
+    // ~ e.g. a loaded board which is clear (or full) already
+    if let Some(finished) = game.is_finished() {
+        describe_finished(game, finished, output)?;
+        return Ok(Some(finished));
+    }
+    describe_nexts(game, output)?;
+    for line in input.lines() {
+        let line = line?;
+        match line.trim() {
+            "" => continue,
+            "quit" | "q" => return Ok(None),
+            "help" | "?" => writeln!(output, "{HELP}")?,
+            "board" => describe_board(game, output)?,
+            "hint" => match game.find_hint() {
+                Some(p) => writeln!(output, "Try row {}, column {}.", p.y + 1, p.x + 1)?,
+                None => writeln!(output, "No clearing placement available.")?,
+            },
+            "undo" => match game.undo() {
+                Some(p) => {
+                    writeln!(output, "Took back row {}, column {}.", p.y + 1, p.x + 1)?;
+                    describe_nexts(game, output)?;
+                }
+                None => writeln!(output, "Nothing to take back.")?,
+            },
+            command => match parse_point(game, command) {
+                Ok(point) => place(game, point, output)?,
+                Err(e) => writeln!(output, "{e}")?,
+            },
+        }
+        if let Some(finished) = game.is_finished() {
+            describe_finished(game, finished, output)?;
+            return Ok(Some(finished));
+        }
+    }
+    Ok(None)
+}
+
+// ~ tells how the game finished
+fn describe_finished<R, W: Write>(
+    game: &Game<R>,
+    finished: Finished,
+    output: &mut W,
+) -> io::Result<()> {
+    match finished {
+        Finished::Success => writeln!(
+            output,
+            "The board is clear after {}. Well done!",
+            counted(game.num_placed(), "placement")
+        ),
+        Finished::Failure => writeln!(
+            output,
+            "Game over; no placement can clear any numbers anymore."
+        ),
+    }
+}
+
+// ~ parses a "ROW,COL" command into a free cell of the board
+fn parse_point<R>(game: &Game<R>, command: &str) -> Result<Cursor, String> {
+    let parsed = command
+        .split_once(',')
+        .and_then(|(row, col)| Some((row.trim().parse().ok()?, col.trim().parse().ok()?)));
+    let Some((row, col)) = parsed else {
+        return Err(format!(
+            "Unknown command: {command:?}; type 'help' for the commands."
+        ));
+    };
+    if !(1..=game.rows()).contains(&row) || !(1..=game.cols()).contains(&col) {
+        return Err(format!(
+            "Row {row}, column {col} is not on the board of {} rows and {} columns.",
+            game.rows(),
+            game.cols()
+        ));
+    }
+    if let Some(s) = game.get(row - 1, col - 1) {
+        return Err(format!("Row {row}, column {col} is taken by a {s}."));
+    }
+    Ok(Cursor {
+        x: (col - 1) as u8,
+        y: (row - 1) as u8,
+    })
+}
+
+// ~ places the next stone at `point` and describes the outcome
+fn place<R: Rng, W: Write>(game: &mut Game<R>, point: Cursor, output: &mut W) -> io::Result<()> {
+    // ~ nothing to place once a finite bag ran out
+    if game.nexts().next().is_none() {
+        return Ok(());
+    }
+    let (row, col) = (point.y + 1, point.x + 1);
+    match game.place_next(point) {
+        PlaceOutcome::Occupied(p) => writeln!(
+            output,
+            "Placed {} at row {row}, column {col}; it stays.",
+            p.stone
+        )?,
+        PlaceOutcome::Cleared(p) => writeln!(
+            output,
+            "Placed {} at row {row}, column {col}; it cleared {}.",
+            p.stone,
+            counted(p.cleared.len(), "number")
+        )?,
+        PlaceOutcome::Refused => {
+            return writeln!(
+                output,
+                "Nothing around row {row}, column {col} to clear; place next to a number."
+            );
+        }
+    }
+    writeln!(
+        output,
+        "{} left on the board; {} so far.",
+        counted(game.num_remaining(), "number"),
+        counted(game.num_placed(), "placement")
+    )?;
+    describe_nexts(game, output)
+}
+
+// ~ lists the numbers of the board row by row; a dot for free places
+fn describe_board<R, W: Write>(game: &Game<R>, output: &mut W) -> io::Result<()> {
+    for (i, row) in game.rows_iter().enumerate() {
+        write!(output, "Row {}:", i + 1)?;
+        for cell in row {
+            match cell {
+                Some(s) => write!(output, " {s}")?,
+                None => write!(output, " .")?,
+            }
+        }
+        writeln!(output)?;
+    }
+    Ok(())
+}
+
+// ~ tells the numbers to come
+fn describe_nexts<R, W: Write>(game: &Game<R>, output: &mut W) -> io::Result<()> {
+    let mut nexts = game.nexts();
+    match nexts.next() {
+        Some(next) => {
+            write!(output, "Next: {next}")?;
+            let rest: Vec<_> = nexts.map(|s| s.to_string()).collect();
+            if !rest.is_empty() {
+                write!(output, "; then {}", rest.join(" "))?;
+            }
+            writeln!(output, ".")
+        }
+        None => writeln!(output, "No numbers left to place."),
+    }
+}
+
+// ~ `n` along with the `noun` in singular or plural, e.g. "1 number"
+// but "2 numbers"
+fn counted(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("{n} {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::play;
+    use crate::{
+        game::{Finished, Game},
+        rng::Pcg32,
+    };
+
+    // ~ a game restored from the given board (rows separated by
+    // newlines) and the stones to come
+    fn game(board: &str, nexts: &str) -> Game<Pcg32> {
+        let (rows, cols) = (board.lines().count(), board.find('\n').unwrap());
+        let saved = format!("summing-save 1\n{rows} {cols}\n0\n{nexts}\n{board}\n");
+        Game::load_full_from_reader(saved.as_bytes(), Pcg32::seed_from_u64(7)).unwrap()
+    }
+
+    // ~ the output of playing `game` by the commands given in `input`
+    fn play_str(game: &mut Game<Pcg32>, input: &str) -> (Option<Finished>, String) {
+        let mut output = Vec::new();
+        let finished = play(game, input.as_bytes(), &mut output).unwrap();
+        (finished, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_play() {
+        let mut game = game("1...\n....\n..23", "31");
+        let input = "help\n0,1\n1,1\n2,x\n2,2\n3,1\nboard\nundo\nquit\n2,2\n";
+        let (finished, output) = play_str(&mut game, input);
+        assert!(finished.is_none());
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(
+            [
+                "Clear the board of 3 rows and 4 columns; type 'help' for the commands.",
+                "Row 1: 1 . . .",
+                "Row 2: . . . .",
+                "Row 3: . . 2 3",
+                "Next: 3; then 1.",
+                "Commands:",
+            ],
+            lines[..6]
+        );
+        // ~ past the help
+        let lines: Vec<_> = lines[6..]
+            .iter()
+            .copied()
+            .skip_while(|l| l.starts_with(' '))
+            .collect();
+        assert_eq!(
+            [
+                "Row 0, column 1 is not on the board of 3 rows and 4 columns.",
+                "Row 1, column 1 is taken by a 1.",
+                "Unknown command: \"2,x\"; type 'help' for the commands.",
+                "Placed 3 at row 2, column 2; it cleared 2 numbers.",
+                "1 number left on the board; 1 placement so far.",
+                "Next: 1; then 9.",
+                "Placed 1 at row 3, column 1; it stays.",
+                "2 numbers left on the board; 2 placements so far.",
+                lines[8],
+                "Row 1: . . . .",
+                "Row 2: . . . .",
+                "Row 3: 1 . . 3",
+                "Took back row 3, column 1.",
+                "Next: 1; then 9.",
+            ],
+            lines[..]
+        );
+        assert_eq!(1, game.num_placed());
+    }
+
+    #[test]
+    fn test_play_undo() {
+        let mut game = game("1..\n...\n...", "56");
+        let input = "3,3\nboard\nundo\nundo\nquit\n1,2\n";
+        let (finished, output) = play_str(&mut game, input);
+        assert!(finished.is_none());
+        let lines: Vec<_> = output.lines().skip(5).collect();
+        assert_eq!(
+            [
+                "Placed 5 at row 3, column 3; it stays.",
+                "2 numbers left on the board; 1 placement so far.",
+                lines[2],
+                "Row 1: 1 . .",
+                "Row 2: . . .",
+                "Row 3: . . 5",
+                "Took back row 3, column 3.",
+                "Next: 5; then 6.",
+                "Nothing to take back.",
+            ],
+            lines[..]
+        );
+        assert!(lines[2].starts_with("Next: 6; then "));
+        // ~ nothing after quitting
+        assert_eq!(0, game.num_placed());
+    }
+
+    #[test]
+    fn test_play_finished_board() {
+        let mut game = game("...\n...\n...", "1");
+        let (finished, output) = play_str(&mut game, "1,2\n");
+        assert!(matches!(finished, Some(Finished::Success)));
+        assert!(
+            output.ends_with("Row 3: . . .\nThe board is clear after 0 placements. Well done!\n")
+        );
+    }
+
+    #[test]
+    fn test_play_to_the_end() {
+        let mut game = game("1..\n...\n...", "1");
+        let (finished, output) = play_str(&mut game, "1,2\n");
+        assert!(matches!(finished, Some(Finished::Success)));
+        assert!(output.ends_with("The board is clear after 1 placement. Well done!\n"));
+    }
+}
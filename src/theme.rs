@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use ratatui::style::{Color, Style};
+
+use crate::{config, game::NUM_STONES};
+
+/// The built-in color schemes for the stones
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Default,
+    /// Distinct hues safe for the common forms of color blindness
+    Colorblind,
+}
+
+impl Palette {
+    pub fn stone_styles(self) -> [Style; NUM_STONES] {
+        match self {
+            Palette::Default => DEFAULT_STONE_STYLES,
+            Palette::Colorblind => COLORBLIND_STONE_STYLES,
+        }
+    }
+}
+
+/// Loads the styles of the stones from the theme file at `path` (see
+/// `parse_theme`) on top of those of `palette`; falling back to just
+/// the palette if there is no such file.
+pub fn load_theme(path: &Path, palette: Palette) -> Result<[Style; NUM_STONES]> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => parse_theme(&text, palette).with_context(|| format!("{}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(palette.stone_styles()),
+        Err(e) => Err(e).with_context(|| format!("{}", path.display())),
+    }
+}
+
+/// Parses a theme, e.g.
+///
+/// ```toml
+/// [0]
+/// bg = "darkgray"
+/// fg = "white"
+/// [1]
+/// bg = "#ff8800"
+/// ```
+///
+/// giving the background and foreground color of each stone by name,
+/// index, or hex code.  Colors not mentioned are taken from
+/// `palette`.
+pub fn parse_theme(text: &str, palette: Palette) -> Result<[Style; NUM_STONES]> {
+    let mut styles = palette.stone_styles();
+    for entry in config::parse(text)? {
+        let line = entry.line;
+        let Some((digit, part)) = entry.key.split_once('.') else {
+            bail!("line {line}: expected a table per digit, e.g. '[0]'");
+        };
+        let Some(style) = digit.parse::<usize>().ok().and_then(|d| styles.get_mut(d)) else {
+            bail!("line {line}: not a digit: '{digit}'");
+        };
+        let color = match &entry.values[..] {
+            [value] => value.parse::<Color>().ok(),
+            _ => None,
+        };
+        let Some(color) = color else {
+            bail!(
+                "line {line}: invalid {part} color of digit {digit}: {:?}",
+                entry.values
+            );
+        };
+        *style = match part {
+            "bg" => style.bg(color),
+            "fg" => style.fg(color),
+            _ => bail!("line {line}: unknown color '{part}' of digit {digit} (bg, fg)"),
+        };
+    }
+    Ok(styles)
+}
+
+const DEFAULT_STONE_STYLES: [Style; NUM_STONES] = [
+    /* 0 */ Style::new().bg(Color::DarkGray).fg(Color::White),
+    /* 1 */ Style::new().bg(Color::Magenta).fg(Color::White),
+    /* 2 */ Style::new().bg(Color::Blue).fg(Color::White),
+    /* 3 */ Style::new().bg(Color::Red).fg(Color::LightYellow),
+    /* 4 */ Style::new().bg(Color::Yellow).fg(Color::Black),
+    /* 5 */ Style::new().bg(Color::Green).fg(Color::Black),
+    /* 6 */ Style::new().bg(Color::LightBlue).fg(Color::Black),
+    /* 7 */ Style::new().bg(Color::Magenta).fg(Color::Black),
+    /* 8 */ Style::new().bg(Color::DarkGray).fg(Color::Yellow),
+    /* 9 */ Style::new().bg(Color::Gray).fg(Color::Black),
+];
+
+// ~ based on the Okabe-Ito palette (extended by white and grey) whose
+// hues remain distinguishable with deuteranopia, protanopia, and
+// tritanopia; there's no red/green pair the digits would have to
+// disambiguate
+const COLORBLIND_STONE_STYLES: [Style; NUM_STONES] = [
+    /* 0 */ Style::new().bg(Color::Rgb(153, 153, 153)).fg(Color::Black),
+    /* 1 */ Style::new().bg(Color::Rgb(230, 159, 0)).fg(Color::Black),
+    /* 2 */ Style::new().bg(Color::Rgb(86, 180, 233)).fg(Color::Black),
+    /* 3 */ Style::new().bg(Color::Rgb(0, 158, 115)).fg(Color::White),
+    /* 4 */ Style::new().bg(Color::Rgb(240, 228, 66)).fg(Color::Black),
+    /* 5 */ Style::new().bg(Color::Rgb(0, 114, 178)).fg(Color::White),
+    /* 6 */ Style::new().bg(Color::Rgb(213, 94, 0)).fg(Color::White),
+    /* 7 */ Style::new().bg(Color::Rgb(204, 121, 167)).fg(Color::Black),
+    /* 8 */ Style::new().bg(Color::Black).fg(Color::White),
+    /* 9 */ Style::new().bg(Color::White).fg(Color::Black),
+];
+
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Style};
+
+    use super::{COLORBLIND_STONE_STYLES, Palette, parse_theme};
+
+    #[test]
+    fn test_parse_theme() {
+        let text = "[0]\nbg = 'black'\nfg = \"#ff8800\"\n\n[9]\nfg = '42'\n";
+        let styles = parse_theme(text, Palette::Colorblind).unwrap();
+        assert_eq!(
+            Style::new().bg(Color::Black).fg(Color::Rgb(255, 136, 0)),
+            styles[0]
+        );
+        assert_eq!(COLORBLIND_STONE_STYLES[1..9], styles[1..9]);
+        assert_eq!(COLORBLIND_STONE_STYLES[9].fg(Color::Indexed(42)), styles[9]);
+        assert_eq!(
+            Palette::Default.stone_styles(),
+            parse_theme("", Palette::Default).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_theme_errors() {
+        for (text, error) in [
+            (
+                "bg = 'red'",
+                "line 1: expected a table per digit, e.g. '[0]'",
+            ),
+            ("[10]\nbg = 'red'", "line 2: not a digit: '10'"),
+            (
+                "[3]\nbg = 'reddish'",
+                "line 2: invalid bg color of digit 3: [\"reddish\"]",
+            ),
+            (
+                "[3]\nfg = ['red', 'blue']",
+                "line 2: invalid fg color of digit 3: [\"red\", \"blue\"]",
+            ),
+            (
+                "[3]\ncolor = 'red'",
+                "line 2: unknown color 'color' of digit 3 (bg, fg)",
+            ),
+        ] {
+            let e = parse_theme(text, Palette::Default).unwrap_err();
+            assert_eq!(error, e.to_string(), "{text}");
+        }
+    }
+}